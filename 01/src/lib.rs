@@ -0,0 +1,141 @@
+use aoc_common::{Error, ParseError, SolveError, Solution};
+
+const DIGIT_WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// The digits written as numerals in `line`, in order.
+fn get_digits(line: &str) -> Vec<u32> {
+    return line
+        .chars()
+        .filter_map(|char| char.to_digit(10))
+        .collect();
+}
+
+/// The digits in `line` written either as numerals or spelled out, in order. Spelled out digits
+/// may overlap, so `eightwo` holds both 8 and 2.
+fn get_digits_with_words(line: &str) -> Vec<u32> {
+    return line
+        .char_indices()
+        .filter_map(|(i, char)| {
+            if let Some(digit) = char.to_digit(10) {
+                return Some(digit);
+            }
+            return DIGIT_WORDS
+                .iter()
+                .position(|word| line[i..].starts_with(word))
+                .map(|index| index as u32 + 1);
+        })
+        .collect();
+}
+
+/// The first and last digit combined into a two-digit number, or `None` for a line without digits.
+fn get_calibration_value(digits: &[u32]) -> Option<u64> {
+    let (Some(first), Some(last)) = (digits.first(), digits.last()) else {
+        return None;
+    };
+    return Some((first * 10 + last) as u64);
+}
+
+/// The sum of the calibration values of `lines`, reading each line's digits with `get_digits`.
+/// Fails on the first line without any, naming the `kind` of digit that was looked for.
+fn get_calibration_sum(lines: &[String], get_digits: fn(&str) -> Vec<u32>, kind: &str) -> Result<u64, SolveError> {
+    let mut sum: u64 = 0;
+    for (line_index, line) in lines.iter().enumerate() {
+        let Some(calibration_value) = get_calibration_value(&get_digits(line)) else {
+            return Err(SolveError::new(format!("line {} has no {} (at {:?})", line_index + 1, kind, line)));
+        };
+        sum += calibration_value;
+    }
+    return Ok(sum);
+}
+
+fn get_calibration_document(input: &str) -> Result<Vec<String>, ParseError> {
+    return input
+        .lines()
+        .enumerate()
+        .map(|(line_index, line)| {
+            if get_digits_with_words(line).is_empty() {
+                return Err(ParseError::in_line(line_index, line, line, "expected a line containing a digit"));
+            }
+            return Ok(line.to_string());
+        })
+        .collect();
+}
+
+pub fn part1(input: &str) -> Result<u64, Error> {
+    return Ok(Day01::part1(&Day01::parse(input)?)?);
+}
+
+pub fn part2(input: &str) -> Result<u64, Error> {
+    return Ok(Day01::part2(&Day01::parse(input)?)?);
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        return get_calibration_document(input);
+    }
+
+    /// Fails on a line whose digits are all spelled out, which parsing accepts for part 2.
+    fn part1(lines: &Vec<String>) -> Result<u64, SolveError> {
+        return get_calibration_sum(lines, get_digits, "digit written as a numeral");
+    }
+
+    fn part2(lines: &Vec<String>) -> Result<u64, SolveError> {
+        return get_calibration_sum(lines, get_digits_with_words, "digit");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let input = include_str!("../test_input.txt");
+
+        assert_eq!(part1(input), Ok(142));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = include_str!("../test_input_part2.txt");
+
+        assert_eq!(part2(input), Ok(281));
+    }
+
+    #[test]
+    fn part2_overlapping_words() {
+        let input = [
+            "47eight1", // 41
+            "815vd5gnbgone", // 81
+            "xbclfszchvone21", // 11
+            "7ttwofourthreehjhpjmtwogrng4", // 74
+            "twothreeoneseven9qd6", // 26
+            "9dhbgmqgr7threekfhzkqqg", // 93
+        ]
+            .join("\n");
+
+        assert_eq!(part2(&input), Ok(326));
+    }
+
+    #[test]
+    fn line_without_digits_is_reported() {
+        let err = Day01::parse("1abc2\nabc\n").unwrap_err();
+
+        assert_eq!((err.line, err.text.as_str()), (2, "abc"));
+    }
+
+    #[test]
+    fn part1_line_with_only_spelled_out_digits_is_reported() {
+        let input = "two1nine\neightwothree";
+
+        assert_eq!(part2(input), Ok(29 + 83));
+        let err = part1(input).unwrap_err();
+        assert_eq!(err.to_string(), "line 2 has no digit written as a numeral (at \"eightwothree\")");
+    }
+}
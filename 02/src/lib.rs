@@ -0,0 +1,428 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use aoc_common::{parse::integer, Error, ParseError, SolveError, Solution};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+/// The cubes shown in one handful, by color.
+#[derive(Debug)]
+struct Reveal {
+    counts: BTreeMap<String, u32>,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    game_id: u32,
+    reveals: Vec<Reveal>,
+}
+
+fn is_color(word: &str) -> bool {
+    return !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase());
+}
+
+/// A `<count> <color>, <count> <color>, ...` reveal. Each color may appear only once.
+fn get_reveal(line_index: usize, line: &str, reveal_str: &str) -> Result<Reveal, ParseError> {
+    let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+    for cubes_str in reveal_str.split(',') {
+        let cubes_str = cubes_str.trim();
+        let Some((count_str, color)) = cubes_str.split_once(' ') else {
+            return Err(ParseError::in_line(line_index, line, cubes_str, "expected `<count> <color>`"));
+        };
+        if !is_color(color) {
+            return Err(ParseError::in_line(line_index, line, color, "expected a lowercase color name"));
+        }
+        let count: u32 = integer(line_index, line, count_str)?;
+        if counts.insert(color.to_string(), count).is_some() {
+            return Err(ParseError::in_line(line_index, line, color, format!("{} appears more than once in this reveal", color)));
+        }
+    }
+    return Ok(Reveal { counts });
+}
+
+/// A `Game <id>: <reveal>; <reveal>; ...` line.
+fn get_game(line_index: usize, line: &str) -> Result<Game, ParseError> {
+    let Some((game_id_str, reveals_str)) = line.strip_prefix("Game ").and_then(|rest| rest.split_once(": ")) else {
+        return Err(ParseError::in_line(line_index, line, line, "expected `Game <id>: `"));
+    };
+    let game_id: u32 = integer(line_index, line, game_id_str)?;
+    let reveals: Vec<Reveal> = reveals_str
+        .split(';')
+        .map(|reveal_str| get_reveal(line_index, line, reveal_str))
+        .collect::<Result<Vec<Reveal>, ParseError>>()?;
+
+    return Ok(Game { game_id, reveals });
+}
+
+/// Every game in the record, one per line.
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    return input
+        .lines()
+        .enumerate()
+        .map(|(line_index, line)| get_game(line_index, line))
+        .collect();
+}
+
+/// How many cubes of each color the bag holds. Colors the bag does not mention have no cubes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    limits: BTreeMap<String, u32>,
+}
+
+impl Bag {
+    pub fn new<'a>(limits: impl IntoIterator<Item = (&'a str, u32)>) -> Bag {
+        return Bag { limits: limits.into_iter().map(|(color, limit)| (color.to_string(), limit)).collect() };
+    }
+
+    pub fn limit(&self, color: &str) -> u32 {
+        return self.limits.get(color).copied().unwrap_or(0);
+    }
+
+    /// Every color the bag holds with its count, alphabetically.
+    pub fn colors(&self) -> impl Iterator<Item = (&str, u32)> {
+        return self.limits.iter().map(|(color, limit)| (color.as_str(), *limit));
+    }
+}
+
+/// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+impl Default for Bag {
+    fn default() -> Bag {
+        return Bag::new([("red", 12), ("green", 13), ("blue", 14)]);
+    }
+}
+
+/// Parses a comma-separated list of `color=count`, e.g. `red=12,green=13,blue=14`.
+impl FromStr for Bag {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Bag, String> {
+        let mut limits: BTreeMap<String, u32> = BTreeMap::new();
+        for entry in spec.split(',') {
+            let Some((color, limit_str)) = entry.trim().split_once('=') else {
+                return Err(format!("expected `color=count`, found {:?}", entry));
+            };
+            if color.is_empty() || !color.chars().all(|c| c.is_ascii_lowercase()) {
+                return Err(format!("expected a lowercase color name, found {:?}", color));
+            }
+            let limit = limit_str
+                .parse::<u32>()
+                .map_err(|err| format!("invalid count {:?} for {}: {}", limit_str, color, err))?;
+            if limits.insert(color.to_string(), limit).is_some() {
+                return Err(format!("{} is listed more than once", color));
+            }
+        }
+        return Ok(Bag { limits });
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self.limits.iter().map(|(color, limit)| format!("{}={}", color, limit)).collect();
+        write!(f, "{}", entries.join(","))
+    }
+}
+
+/// The first reveal of a game that showed more cubes of a color than the bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 0-based index of the reveal within its game.
+    pub reveal_index: usize,
+    pub color: String,
+    pub count: u32,
+    pub limit: u32,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "reveal {} showed {} {} but the bag holds {}", self.reveal_index + 1, self.count, self.color, self.limit)
+    }
+}
+
+/// Whether a game could have been played with a given bag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameCheck {
+    pub game_id: u32,
+    pub violation: Option<Violation>,
+}
+
+impl GameCheck {
+    pub fn is_feasible(&self) -> bool {
+        return self.violation.is_none();
+    }
+}
+
+pub fn check_game(game: &Game, bag: &Bag) -> GameCheck {
+    let violation = game.reveals
+        .iter()
+        .enumerate()
+        .flat_map(|(reveal_index, reveal)| reveal.counts.iter().map(move |(color, count)| (reveal_index, color, *count)))
+        .find(|(_, color, count)| *count > bag.limit(color))
+        .map(|(reveal_index, color, count)| Violation { reveal_index, color: color.to_string(), count, limit: bag.limit(color) });
+
+    return GameCheck { game_id: game.game_id, violation };
+}
+
+pub fn check_games(games: &[Game], bag: &Bag) -> Vec<GameCheck> {
+    return games
+        .iter()
+        .map(|game| check_game(game, bag))
+        .collect();
+}
+
+pub fn part1(input: &str) -> Result<u64, Error> {
+    return Ok(Day02::part1(&Day02::parse(input)?)?);
+}
+
+/// The smallest bag the reveals could have come from: the most cubes of each color shown at once.
+pub fn get_minimal_bag(game: &Game) -> Bag {
+    let mut limits: BTreeMap<String, u32> = BTreeMap::new();
+    for (color, count) in game.reveals.iter().flat_map(|reveal| reveal.counts.iter()) {
+        let limit = limits.entry(color.clone()).or_insert(0);
+        *limit = std::cmp::max(*limit, *count);
+    }
+    return Bag { limits };
+}
+
+/// The product of the minimal bag's counts over every color the game showed. Any number of
+/// colors is allowed, so it can outgrow every fixed-size integer.
+fn get_game_power(game: &Game) -> BigUint {
+    return get_minimal_bag(game)
+        .limits
+        .values()
+        .map(|count| BigUint::from(*count))
+        .product();
+}
+
+/// What one game showed over all of its reveals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameStats {
+    pub game_id: u32,
+    pub minimal_bag: Bag,
+    pub total_cubes: u64,
+    pub num_reveals: usize,
+    pub power: BigUint,
+}
+
+pub fn get_game_stats(game: &Game) -> GameStats {
+    return GameStats {
+        game_id: game.game_id,
+        minimal_bag: get_minimal_bag(game),
+        total_cubes: game.reveals.iter().flat_map(|reveal| reveal.counts.values()).map(|count| *count as u64).sum(),
+        num_reveals: game.reveals.len(),
+        power: get_game_power(game),
+    };
+}
+
+/// Aggregates over every game in a record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub num_games: usize,
+    /// The color a bag needs the most of to make every game feasible, with that count.
+    pub most_constrained_color: Option<(String, u32)>,
+    pub min_power: BigUint,
+    pub median_power: f64,
+    pub mean_power: f64,
+    pub max_power: BigUint,
+}
+
+pub fn get_summary(stats: &[GameStats]) -> Summary {
+    let mut powers: Vec<&BigUint> = stats.iter().map(|game_stats| &game_stats.power).collect();
+    powers.sort();
+    // Powers too large for an f64 count as infinite.
+    let to_f64 = |power: &BigUint| power.to_f64().unwrap_or(f64::INFINITY);
+    let num_games = powers.len();
+    let median_power = match num_games {
+        0 => 0.0,
+        _ if num_games.is_multiple_of(2) => (to_f64(powers[num_games / 2 - 1]) + to_f64(powers[num_games / 2])) / 2.0,
+        _ => to_f64(powers[num_games / 2]),
+    };
+    let mean_power = match num_games {
+        0 => 0.0,
+        _ => powers.iter().map(|power| to_f64(power)).sum::<f64>() / num_games as f64,
+    };
+
+    let mut color_maxima: BTreeMap<&str, u32> = BTreeMap::new();
+    for (color, count) in stats.iter().flat_map(|game_stats| game_stats.minimal_bag.colors()) {
+        let maximum = color_maxima.entry(color).or_insert(0);
+        *maximum = std::cmp::max(*maximum, count);
+    }
+    // Ties go to the alphabetically first color.
+    let most_constrained_color = color_maxima
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(color, count)| (color.to_string(), count));
+
+    return Summary {
+        num_games,
+        most_constrained_color,
+        min_power: powers.first().map_or(BigUint::ZERO, |power| (*power).clone()),
+        median_power,
+        mean_power,
+        max_power: powers.last().map_or(BigUint::ZERO, |power| (*power).clone()),
+    };
+}
+
+pub fn part2(input: &str) -> Result<BigUint, Error> {
+    return Ok(Day02::part2(&Day02::parse(input)?)?);
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Answer1 = u64;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+        return parse(input);
+    }
+
+    fn part1(games: &Vec<Game>) -> Result<u64, SolveError> {
+        return Ok(check_games(games, &Bag::default())
+            .iter()
+            .filter(|check| check.is_feasible())
+            .map(|check| check.game_id as u64)
+            .sum());
+    }
+
+    fn part2(games: &Vec<Game>) -> Result<BigUint, SolveError> {
+        return Ok(games
+            .iter()
+            .map(get_game_power)
+            .sum());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn part1_example() {
+        let input = include_str!("../test_input.txt");
+
+        assert_eq!(part1(input), Ok(8));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = include_str!("../test_input.txt");
+
+        assert_eq!(part2(input), Ok(BigUint::from(2286_u32)));
+    }
+
+    #[test]
+    fn game_power_example() {
+        let games = parse(include_str!("../test_input.txt")).unwrap();
+        let powers: Vec<BigUint> = games.iter().map(get_game_power).collect();
+
+        assert_eq!(powers, [48_u32, 12, 1560, 630, 36].map(BigUint::from));
+    }
+
+    #[test]
+    fn game_ids_are_summed_without_overflow() {
+        assert_eq!(part1("Game 4294967295: 1 red\nGame 1: 1 red"), Ok(u32::MAX as u64 + 1));
+    }
+
+    #[test]
+    fn total_cubes_do_not_overflow() {
+        let games = parse("Game 1: 4294967295 red; 4294967295 red").unwrap();
+
+        assert_eq!(get_game_stats(&games[0]).total_cubes, 2 * u32::MAX as u64);
+    }
+
+    #[test]
+    fn large_powers_are_counted() {
+        let input = "Game 1: 1000 red, 1000 green, 1000 blue, 1000 yellow\nGame 2: 1 red";
+        assert_eq!(part2(input), Ok(BigUint::from(1_000_000_000_001_u64)));
+
+        // 10^20 is past u64::MAX, but only part 2 and the report look at powers.
+        let input = "Game 1: 1 red\nGame 2: 100000 a, 100000 b, 100000 c, 100000 d";
+        assert_eq!(part1(input), Ok(1));
+        assert_eq!(part2(input).map(|power| power.to_string()), Ok("100000000000000000001".to_string()));
+        let stats: Vec<GameStats> = parse(input).unwrap().iter().map(get_game_stats).collect();
+        assert_eq!(get_summary(&stats).max_power.to_string(), "100000000000000000000");
+    }
+
+    #[test]
+    fn stats_and_summary_example() {
+        let games = parse(include_str!("../test_input.txt")).unwrap();
+        let stats: Vec<GameStats> = games.iter().map(get_game_stats).collect();
+
+        assert_eq!(
+            stats[0],
+            GameStats { game_id: 1, minimal_bag: "red=4,green=2,blue=6".parse().unwrap(), total_cubes: 18, num_reveals: 3, power: BigUint::from(48_u32) }
+        );
+        assert_eq!(
+            get_summary(&stats),
+            Summary {
+                num_games: 5,
+                most_constrained_color: Some(("red".to_string(), 20)),
+                min_power: BigUint::from(12_u32),
+                median_power: 48.0,
+                mean_power: 457.2,
+                max_power: BigUint::from(1560_u32),
+            }
+        );
+    }
+
+    #[test]
+    fn new_colors_count_towards_power() {
+        let games = parse("Game 1: 2 yellow, 3 red; 4 yellow").unwrap();
+
+        assert_eq!(get_minimal_bag(&games[0]), Bag::new([("red", 3), ("yellow", 4)]));
+        assert_eq!(get_game_power(&games[0]), BigUint::from(12_u32));
+    }
+
+    #[test]
+    fn duplicate_colors_and_bad_syntax_are_rejected() {
+        let err = parse("Game 1: 2 red, 3 red").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (18, "red"));
+
+        let err = parse("Game 1: 2 red; 3 Blue!").unwrap_err();
+        assert_eq!(err.text, "Blue!");
+
+        let err = parse("Game 1: 2 red;").unwrap_err();
+        assert_eq!(err.column, 15);
+    }
+
+    #[test]
+    fn bag_spec_is_parsed() {
+        assert_eq!("red=12,green=13,blue=14".parse::<Bag>(), Ok(Bag::default()));
+        assert_eq!("red=1, yellow=2".parse::<Bag>().map(|bag| bag.limit("yellow")), Ok(2));
+        assert!("red=1,red=2".parse::<Bag>().is_err());
+        assert!("red:1".parse::<Bag>().is_err());
+    }
+
+    #[test]
+    fn violations_name_reveal_and_color() {
+        let games = parse(include_str!("../test_input.txt")).unwrap();
+        let checks = check_games(&games, &Bag::default());
+
+        let infeasible: Vec<(u32, Violation)> = checks
+            .into_iter()
+            .filter_map(|check| Some((check.game_id, check.violation?)))
+            .collect();
+        assert_eq!(
+            infeasible,
+            vec![
+                (3, Violation { reveal_index: 0, color: "red".to_string(), count: 20, limit: 12 }),
+                (4, Violation { reveal_index: 2, color: "blue".to_string(), count: 15, limit: 14 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn colors_missing_from_bag_hold_no_cubes() {
+        let games = parse(include_str!("../test_input.txt")).unwrap();
+        let bag: Bag = "red=20,blue=15".parse().unwrap();
+
+        assert!(check_games(&games, &bag).iter().all(|check| !check.is_feasible()));
+    }
+
+    #[test]
+    fn missing_game_id_is_reported() {
+        let err = parse("Game 1: 3 blue\n3 blue, 4 red").unwrap_err();
+
+        assert_eq!(err.line, 2);
+    }
+}
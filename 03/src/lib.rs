@@ -1,3 +1,8 @@
+use aoc_common::{
+    grid::{Grid, Position},
+    Error, ParseError, SolveError, Solution,
+};
+
 #[derive(Debug)]
 struct NumberInfo {
     row: usize,
@@ -6,107 +11,57 @@ struct NumberInfo {
     number: u32,
 }
 
-fn get_number_locations(input: String) -> Vec<NumberInfo> {
+fn parse_number(row: usize, col: usize, number_string: &str) -> Result<u32, ParseError> {
+    return number_string
+        .parse::<u32>()
+        .map_err(|err| ParseError::new(row + 1, col + 1, number_string, err.to_string()));
+}
+
+fn get_number_locations(puzzle: &Grid<char>) -> Result<Vec<NumberInfo>, ParseError> {
     let mut number_locations: Vec<NumberInfo> = vec![];
-    input.lines().enumerate().for_each(|(row, line)| {
-        let mut start_col: usize = 0;
-        let mut number_string: String = "".to_string();
-        for (col, char) in line.chars().enumerate() {
-            if number_string.len() == 0 && char.is_numeric() {
-                number_string = char.to_string();
-                start_col = col;
-            } else if char.is_numeric() {
-                number_string = format!("{}{}", number_string, char);
-            } else {
-                if number_string.len() > 0 {
-                    number_locations.push(NumberInfo {
-                        row,
-                        col: start_col,
-                        length: col - start_col,
-                        number: number_string.parse::<u32>().expect("NaN")
-                    });
-                    number_string = "".to_string();
-                }
+    for (row, line) in puzzle.rows().enumerate() {
+        let mut col: usize = 0;
+        while col < line.len() {
+            if !line[col].is_ascii_digit() {
+                col += 1;
+                continue;
             }
+            let length = line[col..].iter().take_while(|char| char.is_ascii_digit()).count();
+            let number_string: String = line[col..col + length].iter().collect();
+            number_locations.push(NumberInfo { row, col, length, number: parse_number(row, col, &number_string)? });
+            col += length;
         }
-        if number_string.len() != 0 {
-            number_locations.push(NumberInfo {
-                row,
-                col: start_col,
-                length: line.len() - start_col,
-                number: number_string.parse::<u32>().expect("NaN")
-            });
-        }
-    });
-    return number_locations;
-}
-
-fn has_symbol_neighbor(number_info: &NumberInfo, puzzle: &Vec<Vec<char>>) -> bool {
-    let start_col = number_info.col;
-    let end_col = number_info.col + number_info.length - 1;
-    let number_length = number_info.length;
-    let row = number_info.row;
-    let line_length = puzzle[0].len();
-    let num_rows = puzzle.len();
-
-    if start_col != 0 && puzzle[row][start_col - 1] != '.' {
-        return true;
     }
+    return Ok(number_locations);
+}
 
-    if end_col < line_length - 1 && puzzle[row][end_col + 1] != '.' {
-        return true;
-    }
+fn is_symbol(char: &char) -> bool {
+    return *char != '.' && !char.is_ascii_digit();
+}
 
-    for col in ((start_col as i32 - 1) as i32)..=((start_col + number_length) as i32) {
-        if col < 0 || col >= line_length as i32 {
-            continue;
-        }
-        if row != 0 && puzzle[row - 1][col as usize] != '.' {
-            return true;
-        }
-        if row < num_rows - 1 && puzzle[row + 1][col as usize] != '.' {
-            return true;
-        }
-    }
+fn has_symbol_neighbor(number_info: &NumberInfo, puzzle: &Grid<char>) -> bool {
+    let rows = number_info.row.saturating_sub(1)..=number_info.row + 1;
+    let cols = number_info.col.saturating_sub(1)..=number_info.col + number_info.length;
 
-    return false;
+    return puzzle.region(rows, cols).any(|(_, char)| is_symbol(char));
 }
 
-pub fn part1(input: String) -> u32 {
-    let puzzle: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-
-    let number_locations: Vec<NumberInfo> = get_number_locations(input);
+pub fn part1(input: &str) -> Result<u32, Error> {
+    return Ok(Day03::part1(&Day03::parse(input)?)?);
+}
 
-    return number_locations
-        .iter()
-        .filter(|number_info| has_symbol_neighbor(number_info, &puzzle))
-        .map(|number_info| number_info.number)
-        .sum();
+fn is_adjacent(number_info: &NumberInfo, (row, col): Position) -> bool {
+    return number_info.row.abs_diff(row) <= 1
+        && number_info.col <= col + 1
+        && col <= number_info.col + number_info.length;
 }
 
-fn get_gear_ratio(gear_row: usize, gear_col: usize, number_locations: &Vec<NumberInfo>) -> u32 {
+fn get_gear_ratio(gear: Position, number_locations: &[NumberInfo]) -> u32 {
     let adjacent_number_locations: Vec<&NumberInfo> = number_locations
         .iter()
-        .filter(|number_info| {
-            // Check rows
-            if !vec![number_info.row, number_info.row + 1].contains(&gear_row)
-                && !(number_info.row != 0 && gear_row == number_info.row - 1) {
-                return false;
-            }
-
-            // Check cols
-            if number_info.col != 0 && gear_col == number_info.col - 1 {
-                return true;
-            }
-
-            if (number_info.col..=number_info.col + number_info.length).contains(&gear_col) {
-                return true;
-            }
-
-            return false;
-        })
+        .filter(|number_info| is_adjacent(number_info, gear))
         .collect();
-    
+
     if adjacent_number_locations.len() < 2 {
         return 0;
     }
@@ -114,24 +69,47 @@ fn get_gear_ratio(gear_row: usize, gear_col: usize, number_locations: &Vec<Numbe
     return adjacent_number_locations
         .iter()
         .map(|number_info| number_info.number)
-        .fold(1, |product, number| product * number);
+        .product();
 }
 
-pub fn part2(input: String) -> u32 {
-    let puzzle: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+pub fn part2(input: &str) -> Result<u32, Error> {
+    return Ok(Day03::part2(&Day03::parse(input)?)?);
+}
 
-    let number_locations: Vec<NumberInfo> = get_number_locations(input);
+#[derive(Debug)]
+pub struct Schematic {
+    puzzle: Grid<char>,
+    number_locations: Vec<NumberInfo>,
+}
 
-    let mut sum: u32 = 0;
-    puzzle.iter().enumerate().for_each(|(row, line_arr)| {
-        line_arr.iter().enumerate().for_each(|(col, char)| {
-            if *char == '*' {
-                sum += get_gear_ratio(row, col, &number_locations);
-            }
-        })
-    });
-    
-    return sum;
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Schematic, ParseError> {
+        let puzzle: Grid<char> = Grid::parse(input)?;
+        let number_locations: Vec<NumberInfo> = get_number_locations(&puzzle)?;
+        return Ok(Schematic { puzzle, number_locations });
+    }
+
+    fn part1(schematic: &Schematic) -> Result<u32, SolveError> {
+        return Ok(schematic.number_locations
+            .iter()
+            .filter(|number_info| has_symbol_neighbor(number_info, &schematic.puzzle))
+            .map(|number_info| number_info.number)
+            .sum());
+    }
+
+    fn part2(schematic: &Schematic) -> Result<u32, SolveError> {
+        return Ok(schematic.puzzle
+            .positions()
+            .filter(|(_, char)| **char == '*')
+            .map(|(gear, _)| get_gear_ratio(gear, &schematic.number_locations))
+            .sum());
+    }
 }
 
 #[cfg(test)]
@@ -140,15 +118,22 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        let input = include_str!("../input.txt");
+        let input = include_str!("../test_input.txt");
 
-        assert_eq!(part1(input.to_string()), 4361);
+        assert_eq!(part1(input), Ok(4361));
     }
 
     #[test]
     fn part2_example() {
-        let input = include_str!("../input.txt");
+        let input = include_str!("../test_input.txt");
+
+        assert_eq!(part2(input), Ok(467835));
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let err = Day03::parse("467..\n...*\n..35.").unwrap_err();
 
-        assert_eq!(part2(input.to_string()), 467835);
+        assert_eq!((err.line, err.column), (2, 1));
     }
 }
@@ -1,8 +0,0 @@
-use day_3::{part1, part2};
-
-fn main() {
-    let input = include_str!("../input.txt");
-
-    println!("{:?}", part2(input.to_string()));
-
-}
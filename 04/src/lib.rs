@@ -1,89 +1,168 @@
-use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::{
+    parse::{integer, split_keyed_lists, unique_integers},
+    Error, ParseError, SolveError, Solution,
+};
 
 #[derive(Debug)]
-struct ScratchCard {
+pub struct ScratchCard {
     card_num: u32,
     numbers: Vec<u32>,
-    winning_numbers: Vec<u32>,
+    winning_numbers: HashSet<u32>,
 }
 
-fn get_scratch_cards(input: &str) -> Vec<ScratchCard> { let card_regex = Regex::new(r"Card\s+(?<card_num>\d+):").unwrap();
-    let number_regex = Regex::new(r"\b(\d+)\b").unwrap();
-    return input.lines().map(|line| {
-        let Some(card_num_match) = card_regex.captures(line) else {
-            panic!("Could not match card_num");
-        };
-        let card_num: u32 = card_num_match["card_num"].parse::<u32>().unwrap_or(0);
-        let line_arr: Vec<&str> = line.split(":").collect();
-        let all_numbers: &str = line_arr[1];
-        let number_partitions: Vec<&str> = all_numbers.split("|").collect();
-        let numbers: Vec<u32> = number_regex
-            .captures_iter(number_partitions[0])
-            .flat_map(|captures| captures.get(1))
-            .map(|number_str| number_str.as_str().parse::<u32>())
-            .filter_map(Result::ok)
-            .collect();
-        
-        let winning_numbers: Vec<u32> = number_regex
-            .captures_iter(number_partitions[1])
-            .flat_map(|captures| captures.get(1))
-            .map(|number_str| number_str.as_str().parse::<u32>())
-            .filter_map(Result::ok)
-            .collect();
+fn get_scratch_card(line_index: usize, line: &str) -> Result<ScratchCard, ParseError> {
+    let (key, numbers_str, winning_numbers_str) = split_keyed_lists(line_index, line)?;
+    let Some(card_num_str) = key.strip_prefix("Card") else {
+        return Err(ParseError::in_line(line_index, line, key, "expected `Card <number>:`"));
+    };
+    let card_num_str = card_num_str.trim_start();
+    let card_num: u32 = integer(line_index, line, card_num_str)?;
+    if card_num == 0 {
+        return Err(ParseError::in_line(line_index, line, card_num_str, "card numbers start at 1"));
+    }
+    let numbers: Vec<u32> = unique_integers(line_index, line, numbers_str)?;
+    let winning_numbers: Vec<u32> = unique_integers(line_index, line, winning_numbers_str)?;
 
-        return ScratchCard { card_num, winning_numbers, numbers };
-    })
-    .collect();
+    return Ok(ScratchCard { card_num, winning_numbers: winning_numbers.into_iter().collect(), numbers });
+}
 
+/// Every card, ordered by card number. The cards may be listed in any order and numbers may be
+/// skipped, but each number may only be used once.
+fn get_scratch_cards(input: &str) -> Result<Vec<ScratchCard>, ParseError> {
+    let mut card_lines: HashMap<u32, usize> = HashMap::new();
+    let mut scratch_cards: Vec<ScratchCard> = vec![];
+    for (line_index, line) in input.lines().enumerate() {
+        let scratch_card = get_scratch_card(line_index, line)?;
+        if let Some(first_line_index) = card_lines.insert(scratch_card.card_num, line_index) {
+            return Err(ParseError::in_line(
+                line_index,
+                line,
+                line,
+                format!("card {} is already listed on line {}", scratch_card.card_num, first_line_index + 1),
+            ));
+        }
+        scratch_cards.push(scratch_card);
+    }
+    scratch_cards.sort_by_key(|scratch_card| scratch_card.card_num);
+    return Ok(scratch_cards);
 }
 
-fn get_num_matches(scratch_card: &ScratchCard) -> u32 {
+/// The card's numbers that are also winning numbers, in the order they appear on the card.
+fn get_matched_numbers(scratch_card: &ScratchCard) -> Vec<u32> {
     return scratch_card.numbers
         .iter()
-        .filter(|number| scratch_card.winning_numbers.contains(*number))
-        .count() as u32;
+        .filter(|number| scratch_card.winning_numbers.contains(number))
+        .copied()
+        .collect();
+}
+
+fn get_num_matches(scratch_card: &ScratchCard) -> u32 {
+    return get_matched_numbers(scratch_card).len() as u32;
 }
 
-fn get_scratch_card_points(scratch_card: &ScratchCard) -> u32 {
+/// One point for the first match, doubled for each match after it. More than 64 matches score
+/// more points than a `u64` holds.
+fn get_scratch_card_points(scratch_card: &ScratchCard) -> Result<u64, SolveError> {
     let num_matches: u32 = get_num_matches(scratch_card);
     if num_matches == 0 {
-        return 0;
+        return Ok(0);
     }
-    return (2 as u32).pow(num_matches - 1);
+    return 2_u64
+        .checked_pow(num_matches - 1)
+        .ok_or_else(|| SolveError::new(format!("card {} scores more points than fit in 64 bits", scratch_card.card_num)));
 }
 
-fn get_total_points(scratch_cards: Vec<ScratchCard>) -> u32 {
-    return scratch_cards
-        .iter()
-        .map(|scratch_card| get_scratch_card_points(scratch_card))
-        .sum();
+fn get_total_points(scratch_cards: &[ScratchCard]) -> Result<u64, SolveError> {
+    let mut total_points: u64 = 0;
+    for scratch_card in scratch_cards {
+        total_points = total_points
+            .checked_add(get_scratch_card_points(scratch_card)?)
+            .ok_or_else(|| SolveError::new(format!("the total points up to card {} do not fit in 64 bits", scratch_card.card_num)))?;
+    }
+    return Ok(total_points);
 }
 
-pub fn part1(input: &str) -> u32 {
-    return get_total_points(get_scratch_cards(input));
+pub fn part1(input: &str) -> Result<u64, Error> {
+    return Ok(Day04::part1(&Day04::parse(input)?)?);
 }
 
-fn get_num_scratch_card_copies(scratch_card: &ScratchCard, scratch_cards: &Vec<ScratchCard>) -> u32 {
-    let index: usize = scratch_card.card_num.clone() as usize - 1;
-    let num_matches: u32 = get_num_matches(scratch_card);
-    if scratch_card.card_num == scratch_cards.len() as u32 {
-        return 1;
-    }
-    if num_matches == 0 {
-        return 1;
+/// How many copies of each card (ordered by card number) are held once every card has been
+/// scored, in a single pass: a card only wins copies of higher-numbered cards, so its own count
+/// is final when it is reached. A card with `n` matches wins the cards numbered up to `n` above
+/// it; numbers missing from the list win nothing. Fails once a card's count, or the running
+/// total of copies, no longer fits in a `u64`, so the counts always sum without overflow.
+fn get_num_scratch_card_copies(scratch_cards: &[ScratchCard]) -> Result<Vec<u64>, SolveError> {
+    let mut copies: Vec<u64> = vec![1; scratch_cards.len()];
+    let mut total_copies: u64 = 0;
+    for (index, scratch_card) in scratch_cards.iter().enumerate() {
+        total_copies = total_copies
+            .checked_add(copies[index])
+            .ok_or_else(|| SolveError::new(format!("the copies of cards up to card {} do not fit in 64 bits", scratch_card.card_num)))?;
+        let last_won_card_num = scratch_card.card_num as u64 + get_num_matches(scratch_card) as u64;
+        for won_index in (index + 1)..scratch_cards.len() {
+            let won_card_num = scratch_cards[won_index].card_num;
+            if won_card_num as u64 > last_won_card_num {
+                break;
+            }
+            copies[won_index] = copies[won_index]
+                .checked_add(copies[index])
+                .ok_or_else(|| SolveError::new(format!("card {} has more copies than fit in 64 bits", won_card_num)))?;
+        }
     }
-    return 1 + ((index+1)..=index+num_matches as usize)
-        .into_iter()
-        .map(|i| get_num_scratch_card_copies(&scratch_cards[i], scratch_cards))
-        .sum::<u32>();
+    return Ok(copies);
 }
 
-pub fn part2(input: &str) -> u32 {
-    let scratch_cards: Vec<ScratchCard> = get_scratch_cards(input);
+/// How one card scored and how many copies of it were held at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardBreakdown {
+    pub card_num: u32,
+    pub matched_numbers: Vec<u32>,
+    pub points: u64,
+    pub copies: u64,
+}
+
+/// Every card's breakdown. It is shown together with the answers to both parts, so it fails
+/// like either part would.
+pub fn get_breakdown(scratch_cards: &[ScratchCard]) -> Result<Vec<CardBreakdown>, SolveError> {
+    get_total_points(scratch_cards)?;
     return scratch_cards
         .iter()
-        .map(|scratch_card| get_num_scratch_card_copies(scratch_card, &scratch_cards))
-        .sum();
+        .zip(get_num_scratch_card_copies(scratch_cards)?)
+        .map(|(scratch_card, copies)| {
+            return Ok(CardBreakdown {
+                card_num: scratch_card.card_num,
+                matched_numbers: get_matched_numbers(scratch_card),
+                points: get_scratch_card_points(scratch_card)?,
+                copies,
+            });
+        })
+        .collect();
+}
+
+pub fn part2(input: &str) -> Result<u64, Error> {
+    return Ok(Day04::part2(&Day04::parse(input)?)?);
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<ScratchCard>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<ScratchCard>, ParseError> {
+        return get_scratch_cards(input);
+    }
+
+    fn part1(scratch_cards: &Vec<ScratchCard>) -> Result<u64, SolveError> {
+        return get_total_points(scratch_cards);
+    }
+
+    fn part2(scratch_cards: &Vec<ScratchCard>) -> Result<u64, SolveError> {
+        return Ok(get_num_scratch_card_copies(scratch_cards)?.iter().sum());
+    }
 }
 
 #[cfg(test)]
@@ -92,15 +171,96 @@ mod tests {
 
     #[test]
     fn part1_example() {
-        let input = include_str!("../input.txt");
+        let input = include_str!("../test_input.txt");
 
-        assert_eq!(part1(input), 13);
+        assert_eq!(part1(input), Ok(13));
     }
 
     #[test]
     fn part2_example() {
-        let input = include_str!("../input.txt");
+        let input = include_str!("../test_input.txt");
+
+        assert_eq!(part2(input), Ok(30));
+    }
+
+    #[test]
+    fn breakdown_example() {
+        let scratch_cards = Day04::parse(include_str!("../test_input.txt")).unwrap();
+        let breakdown = get_breakdown(&scratch_cards).unwrap();
+
+        assert_eq!(breakdown[0], CardBreakdown { card_num: 1, matched_numbers: vec![48, 83, 86, 17], points: 8, copies: 1 });
+        assert_eq!(breakdown.iter().map(|card| card.copies).collect::<Vec<u64>>(), vec![1, 2, 4, 8, 14, 1]);
+    }
+
+    #[test]
+    fn part2_many_cards() {
+        // Every card wins one copy of the next, so card n ends up with n copies.
+        let num_cards: u64 = 5000;
+        let input: String = (1..=num_cards)
+            .map(|card_num| format!("Card {}: {} 1 2 3 4 | {} 5 6 7 8 9 10 11\n", card_num, card_num + 100, card_num + 100))
+            .collect();
+
+        assert_eq!(part2(&input), Ok(num_cards * (num_cards + 1) / 2));
+    }
+
+    #[test]
+    fn out_of_order_and_gapped_cards_are_matched_by_number() {
+        // Card 3 wins cards 4 (missing) and 5, card 1 wins card 2 (missing).
+        let input = "Card 5: 1 | 2\nCard 3: 1 2 | 2 1\nCard 1: 7 | 7\n";
+        let scratch_cards = Day04::parse(input).unwrap();
+
+        assert_eq!(scratch_cards.iter().map(|scratch_card| scratch_card.card_num).collect::<Vec<u32>>(), vec![1, 3, 5]);
+        assert_eq!(get_num_scratch_card_copies(&scratch_cards), Ok(vec![1, 1, 2]));
+    }
+
+    #[test]
+    fn bad_card_numbering_is_reported() {
+        let err = Day04::parse("Card 1: 1 | 2\nCard 1: 3 | 4").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "card 1 is already listed on line 1"));
+
+        let err = Day04::parse("Card 0: 1 | 2").unwrap_err();
+        assert_eq!(err.text, "0");
+    }
+
+    #[test]
+    fn duplicate_number_on_a_side_is_reported() {
+        let err = Day04::parse("Card 1: 41 48 | 83 48 83").unwrap_err();
+
+        assert_eq!((err.column, err.text.as_str()), (23, "83"));
+    }
+
+    #[test]
+    fn points_beyond_u64_are_reported() {
+        let numbers = |count: u32| (1..=count).map(|number| number.to_string()).collect::<Vec<String>>().join(" ");
+        let input = format!("Card 1: {} | {}", numbers(64), numbers(64));
+        assert_eq!(part1(&input), Ok(1 << 63));
+
+        // Part 2 only counts matches, so it still has an answer.
+        let input = format!("Card 1: 1 | 2\nCard 2: {} | {}", numbers(65), numbers(65));
+        assert_eq!(part1(&input), Err(Error::Solve(SolveError::new("card 2 scores more points than fit in 64 bits"))));
+        assert_eq!(part2(&input), Ok(2));
+
+        let input = format!("Card 1: {} | {}\nCard 2: {} | {}", numbers(64), numbers(64), numbers(64), numbers(64));
+        let err = Day04::part1(&Day04::parse(&input).unwrap()).unwrap_err();
+        assert_eq!(err.message, "the total points up to card 2 do not fit in 64 bits");
+    }
+
+    #[test]
+    fn copies_beyond_u64_are_reported() {
+        // Every card wins the next two, so the copies grow like the Fibonacci numbers and the
+        // running total passes u64::MAX at card 90.
+        let input: String = (1..=100)
+            .map(|card_num| format!("Card {}: 1 2 | 1 2\n", card_num))
+            .collect();
+
+        assert_eq!(part1(&input), Ok(200));
+        assert_eq!(part2(&input), Err(Error::Solve(SolveError::new("the copies of cards up to card 90 do not fit in 64 bits"))));
+    }
+
+    #[test]
+    fn malformed_number_is_reported() {
+        let err = Day04::parse("Card 1: 41 48 | 83 x6").unwrap_err();
 
-        assert_eq!(part2(input), 30);
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 20, "x6"));
     }
 }
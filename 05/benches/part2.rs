@@ -0,0 +1,57 @@
+use aoc_common::Solution;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day_05::Day05;
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+/// Small linear congruential generator, so every run benchmarks the same almanac.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        return (self.0 >> 33) % bound;
+    }
+}
+
+/// An almanac shaped like the real ones: each block cuts `0..size * 1000` into `size` tiles and
+/// moves them to shuffled places, shifted by up to a tile so no two blocks cut the values in the
+/// same places, and there are `size` short seed ranges.
+fn get_almanac_input(size: usize) -> String {
+    let mut rng = Lcg(size as u64);
+    let span = size as u64 * 1000;
+    let seeds: Vec<String> = (0..size).map(|_| format!("{} {}", rng.next(span), rng.next(200) + 1)).collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    for pair in CATEGORIES.windows(2) {
+        input += &format!("\n{}-to-{} map:\n", pair[0], pair[1]);
+        let shift = rng.next(1000);
+        let mut order: Vec<u64> = (0..size as u64).collect();
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.next(i as u64 + 1) as usize);
+        }
+        for (tile, destination_tile) in order.iter().enumerate() {
+            input += &format!("{} {} 1000\n", destination_tile * 1000 + shift, tile as u64 * 1000);
+        }
+    }
+    return input;
+}
+
+/// Times parsing, which composes the blocks into one map, together with part 2 on ever larger
+/// almanacs; near-linear scaling shows as a flat time per element.
+fn bench_part2(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_05_parse_and_part2");
+    group.sample_size(10);
+    for size in [1_000, 10_000, 40_000] {
+        let input = get_almanac_input(size);
+        let lowest_location = Day05::part2(&Day05::parse(&input).unwrap());
+        assert!(matches!(lowest_location, Ok(location) if location > 0), "degenerate almanac: {:?}", lowest_location);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &input, |bencher, input| {
+            bencher.iter(|| Day05::part2(&Day05::parse(input).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_part2);
+criterion_main!(benches);
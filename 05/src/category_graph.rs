@@ -0,0 +1,159 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt,
+    ops::Range,
+};
+
+use aoc_common::ParseError;
+
+use crate::IntervalMap;
+
+/// One `<from>-to-<to> map:` block, with where its header sits for error messages.
+#[derive(Debug)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub map: IntervalMap,
+    pub line_index: usize,
+    pub header: String,
+}
+
+/// Why two categories could not be mapped onto each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryError {
+    Unknown(String),
+    Unreachable { from: String, to: String },
+}
+
+impl fmt::Display for CategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoryError::Unknown(category) => write!(f, "the almanac has no {} category", category),
+            CategoryError::Unreachable { from, to } => write!(f, "the almanac has no chain of maps from {} to {}", from, to),
+        }
+    }
+}
+
+impl std::error::Error for CategoryError {}
+
+/// The categories of an almanac and the maps between them, as read from the block headers.
+/// Maps only ever lead forward, so the graph has no cycles.
+#[derive(Debug, Default)]
+pub struct CategoryGraph {
+    edges: BTreeMap<String, Vec<Edge>>,
+    categories: BTreeSet<String>,
+}
+
+impl CategoryGraph {
+    /// Builds the graph from its maps, rejecting a repeated `from`-`to` pair or maps that lead
+    /// back to where they started.
+    pub fn new(edges: impl IntoIterator<Item = Edge>) -> Result<CategoryGraph, ParseError> {
+        let mut graph = CategoryGraph::default();
+        for edge in edges {
+            let outgoing = graph.edges.entry(edge.from.clone()).or_default();
+            if let Some(existing) = outgoing.iter().find(|existing| existing.to == edge.to) {
+                return Err(ParseError::new(
+                    edge.line_index + 1,
+                    1,
+                    &edge.header,
+                    format!("{} to {} is already mapped on line {}", edge.from, edge.to, existing.line_index + 1),
+                ));
+            }
+            graph.categories.insert(edge.from.clone());
+            graph.categories.insert(edge.to.clone());
+            outgoing.push(edge);
+        }
+        graph.check_acyclic()?;
+        return Ok(graph);
+    }
+
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        return self.categories.iter().map(|category| category.as_str());
+    }
+
+    fn outgoing(&self, from: &str) -> &[Edge] {
+        return self.edges.get(from).map_or(&[], |edges| edges.as_slice());
+    }
+
+    /// Depth-first search keeping the current path, so a map back onto the path is a cycle.
+    fn check_acyclic(&self) -> Result<(), ParseError> {
+        let mut finished: BTreeSet<&str> = BTreeSet::new();
+        for start in self.categories() {
+            let mut path: Vec<&str> = vec![];
+            self.visit(start, &mut path, &mut finished)?;
+        }
+        return Ok(());
+    }
+
+    fn visit<'a>(&'a self, category: &'a str, path: &mut Vec<&'a str>, finished: &mut BTreeSet<&'a str>) -> Result<(), ParseError> {
+        if finished.contains(category) {
+            return Ok(());
+        }
+        path.push(category);
+        for edge in self.outgoing(category) {
+            if let Some(cycle_start) = path.iter().position(|on_path| *on_path == edge.to) {
+                let cycle: Vec<&str> = path[cycle_start..].iter().copied().chain([edge.to.as_str()]).collect();
+                return Err(ParseError::new(
+                    edge.line_index + 1,
+                    1,
+                    &edge.header,
+                    format!("maps form a cycle: {}", cycle.join(" -> ")),
+                ));
+            }
+            self.visit(&edge.to, path, finished)?;
+        }
+        path.pop();
+        finished.insert(category);
+        return Ok(());
+    }
+
+    /// The maps to apply in turn to get from `from` to `to`, following the fewest maps.
+    pub fn get_path(&self, from: &str, to: &str) -> Result<Vec<&Edge>, CategoryError> {
+        for category in [from, to] {
+            if !self.categories.contains(category) {
+                return Err(CategoryError::Unknown(category.to_string()));
+            }
+        }
+
+        let mut reached_by: BTreeMap<&str, &Edge> = BTreeMap::new();
+        let mut queue: VecDeque<&str> = VecDeque::from([from]);
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut path: Vec<&Edge> = vec![];
+                let mut current = to;
+                while current != from {
+                    let edge = reached_by[current];
+                    path.push(edge);
+                    current = &edge.from;
+                }
+                path.reverse();
+                return Ok(path);
+            }
+            for edge in self.outgoing(category) {
+                if edge.to != from && !reached_by.contains_key(edge.to.as_str()) {
+                    reached_by.insert(&edge.to, edge);
+                    queue.push_back(&edge.to);
+                }
+            }
+        }
+        return Err(CategoryError::Unreachable { from: from.to_string(), to: to.to_string() });
+    }
+
+    /// The single map taking values in category `from` to category `to`.
+    pub fn get_mapping(&self, from: &str, to: &str) -> Result<IntervalMap, CategoryError> {
+        return Ok(self
+            .get_path(from, to)?
+            .iter()
+            .fold(IntervalMap::identity(), |composed, edge| composed.then(&edge.map)));
+    }
+
+    /// Every value in category `from` that ends up in one of `ranges` of category `to`, found by
+    /// taking the ranges back through each map on the way, last map first.
+    pub fn get_preimage(&self, from: &str, to: &str, ranges: &[Range<u64>]) -> Result<Vec<Range<u64>>, CategoryError> {
+        return Ok(self
+            .get_path(from, to)?
+            .iter()
+            .rev()
+            .fold(ranges.to_vec(), |ranges, edge| edge.map.preimage(&ranges)));
+    }
+}
@@ -0,0 +1,300 @@
+use std::{collections::BTreeMap, ops::Range};
+
+/// Maps the values `start..end` onto `destination..destination + (end - start)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: u64,
+    pub end: u64,
+    pub destination: u64,
+}
+
+impl Segment {
+    fn apply(&self, value: u64) -> u64 {
+        return self.destination + (value - self.start);
+    }
+
+    fn is_identity(&self) -> bool {
+        return self.start == self.destination;
+    }
+}
+
+/// `ranges` sorted by start with overlapping or touching ranges merged and empty ones dropped.
+pub fn normalize_ranges(ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+    let mut sorted: Vec<Range<u64>> = ranges.iter().filter(|range| !range.is_empty()).cloned().collect();
+    sorted.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<u64>> = vec![];
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    return merged;
+}
+
+/// A map from `u64` to `u64` made of shifted intervals; values outside every interval map to
+/// themselves. The segments are kept sorted, disjoint and free of identity pieces, so two maps
+/// doing the same thing compare equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalMap {
+    segments: Vec<Segment>,
+}
+
+impl IntervalMap {
+    pub fn identity() -> IntervalMap {
+        return IntervalMap::default();
+    }
+
+    /// Builds the map for almanac entries of `(destination, source, length)`. Where entries
+    /// overlap, the one listed first wins. Entries are cut short rather than run past `u64::MAX`.
+    pub fn from_entries(entries: impl IntoIterator<Item = (u64, u64, u64)>) -> IntervalMap {
+        let mut covered: BTreeMap<u64, Segment> = BTreeMap::new();
+        for (destination, source, length) in entries {
+            let length = length.min(u64::MAX - source).min(u64::MAX - destination);
+            let end = source + length;
+            if length == 0 {
+                continue;
+            }
+
+            // The segment starting at or before `source` may still reach into this entry.
+            let first_start = covered.range(..=source).next_back().map_or(source, |(start, _)| *start);
+            let mut cursor = source;
+            let mut gaps: Vec<Range<u64>> = vec![];
+            for segment in covered.range(first_start..end).map(|(_, segment)| segment) {
+                if segment.start > cursor {
+                    gaps.push(cursor..segment.start);
+                }
+                cursor = cursor.max(segment.end);
+            }
+            if cursor < end {
+                gaps.push(cursor..end);
+            }
+            for gap in gaps {
+                covered.insert(gap.start, Segment { start: gap.start, end: gap.end, destination: destination + (gap.start - source) });
+            }
+        }
+        return IntervalMap::from_sorted_segments(covered.into_values());
+    }
+
+    /// Drops identity segments and merges neighbours that continue the same shift.
+    fn from_sorted_segments(segments: impl IntoIterator<Item = Segment>) -> IntervalMap {
+        let mut merged: Vec<Segment> = vec![];
+        for segment in segments.into_iter().filter(|segment| !segment.is_identity() && segment.start < segment.end) {
+            match merged.last_mut() {
+                Some(last) if last.end == segment.start && last.apply(last.end - 1) + 1 == segment.destination => {
+                    last.end = segment.end;
+                }
+                _ => merged.push(segment),
+            }
+        }
+        return IntervalMap { segments: merged };
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        return &self.segments;
+    }
+
+    pub fn get(&self, value: u64) -> u64 {
+        let index = self.segments.partition_point(|segment| segment.end <= value);
+        return match self.segments.get(index) {
+            Some(segment) if segment.start <= value => segment.apply(value),
+            _ => value,
+        };
+    }
+
+    /// Splits `range` at segment boundaries, pairing each piece with where its first value maps.
+    fn split(&self, range: Range<u64>) -> Vec<(Range<u64>, u64)> {
+        let first_index = self.segments.partition_point(|segment| segment.end <= range.start);
+        return self.split_from(first_index, range);
+    }
+
+    /// Like [`IntervalMap::split`], for a `range` no segment before `first_index` reaches.
+    fn split_from(&self, first_index: usize, range: Range<u64>) -> Vec<(Range<u64>, u64)> {
+        let mut pieces: Vec<(Range<u64>, u64)> = vec![];
+        let mut cursor = range.start;
+        for segment in self.segments[first_index..].iter() {
+            if segment.start >= range.end {
+                break;
+            }
+            if segment.start > cursor {
+                pieces.push((cursor..segment.start, cursor));
+                cursor = segment.start;
+            }
+            let piece_end = segment.end.min(range.end);
+            pieces.push((cursor..piece_end, segment.apply(cursor)));
+            cursor = piece_end;
+        }
+        if cursor < range.end {
+            pieces.push((cursor..range.end, cursor));
+        }
+        return pieces;
+    }
+
+    /// Where the values of `range` end up, as one range per piece of the map they fall in.
+    pub fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        return self
+            .split(range)
+            .into_iter()
+            .map(|(piece, destination)| destination..destination + (piece.end - piece.start))
+            .collect();
+    }
+
+    /// Where the values of all of `ranges` end up, as sorted, merged ranges. The ranges are
+    /// merged and sorted once and swept alongside the segments. A segment is looked at once for
+    /// every range it overlaps, plus once by the range that stops before it, but the merged
+    /// ranges are disjoint, so for `n` segments and `m` ranges there are fewer than `n + 2m` such
+    /// looks. Sorting the pieces they produce makes the whole `O((n + m) log (n + m))`.
+    pub fn map_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut mapped: Vec<Range<u64>> = vec![];
+        let mut first_index = 0;
+        for range in normalize_ranges(ranges) {
+            while self.segments.get(first_index).is_some_and(|segment| segment.end <= range.start) {
+                first_index += 1;
+            }
+            for (piece, destination) in self.split_from(first_index, range) {
+                mapped.push(destination..destination + (piece.end - piece.start));
+            }
+        }
+        return normalize_ranges(&mapped);
+    }
+
+    /// Every value that maps into one of `ranges`, as sorted, merged ranges. Several pieces of
+    /// the map can land on the same values, so the result may be larger than `ranges`.
+    pub fn preimage(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let targets = normalize_ranges(ranges);
+        let mut sources: Vec<Range<u64>> = vec![];
+        for (piece, destination) in self.split(0..u64::MAX) {
+            let image_end = destination + (piece.end - piece.start);
+            for target in targets.iter() {
+                let overlap_start = target.start.max(destination);
+                let overlap_end = target.end.min(image_end);
+                if overlap_start < overlap_end {
+                    sources.push(piece.start + (overlap_start - destination)..piece.start + (overlap_end - destination));
+                }
+            }
+        }
+        return normalize_ranges(&sources);
+    }
+
+    /// The single map that applies `self` and then `next`.
+    pub fn then(&self, next: &IntervalMap) -> IntervalMap {
+        let mut segments: Vec<Segment> = vec![];
+        for (piece, destination) in self.split(0..u64::MAX) {
+            let image = destination..destination + (piece.end - piece.start);
+            for (next_piece, next_destination) in next.split(image) {
+                let start = piece.start + (next_piece.start - destination);
+                segments.push(Segment { start, end: start + (next_piece.end - next_piece.start), destination: next_destination });
+            }
+        }
+        return IntervalMap::from_sorted_segments(segments);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn seed_to_soil() -> IntervalMap {
+        return IntervalMap::from_entries([(50, 98, 2), (52, 50, 48)]);
+    }
+
+    #[test]
+    fn looks_up_points() {
+        let map = seed_to_soil();
+
+        assert_eq!([79, 14, 55, 13, 98, 99, 100].map(|seed| map.get(seed)), [81, 14, 57, 13, 50, 51, 100]);
+    }
+
+    #[test]
+    fn maps_ranges_piecewise() {
+        assert_eq!(seed_to_soil().map_range(45..100), vec![45..50, 52..100, 50..52]);
+    }
+
+    #[test]
+    fn maps_many_ranges_into_merged_ranges() {
+        // 98..100 lands on 50..52, right before where 50..55 lands, so the two merge.
+        assert_eq!(seed_to_soil().map_ranges(&[98..100, 50..55, 0..3, 2..5]), vec![0..5, 50..57]);
+    }
+
+    #[test]
+    fn preimage_finds_every_source() {
+        // 50 and 51 come from seeds 98 and 99, while soil 52 comes from seed 50.
+        assert_eq!(seed_to_soil().preimage(&[50..52, 52..53]), vec![50..51, 98..100]);
+        assert_eq!(normalize_ranges(&[5..8, 0..2, 2..3, 6..9, 4..4]), vec![0..3, 5..9]);
+    }
+
+    #[test]
+    fn first_listed_entry_wins_overlaps() {
+        let map = IntervalMap::from_entries([(100, 10, 5), (200, 0, 20)]);
+
+        assert_eq!([5, 10, 14, 15].map(|value| map.get(value)), [205, 100, 104, 215]);
+    }
+
+    #[test]
+    fn composes_into_flat_map() {
+        let swap = IntervalMap::from_entries([(10, 0, 5), (0, 10, 5)]);
+        let shift = IntervalMap::from_entries([(20, 10, 5)]);
+
+        assert_eq!(swap.then(&swap), IntervalMap::identity());
+        assert_eq!(swap.then(&shift).segments(), &[Segment { start: 0, end: 5, destination: 20 }, Segment { start: 10, end: 15, destination: 0 }]);
+    }
+
+    fn entries() -> impl Strategy<Value = Vec<(u64, u64, u64)>> {
+        return prop::collection::vec((0..200_u64, 0..200_u64, 0..50_u64), 0..8);
+    }
+
+    /// Applies the entries the way the puzzle describes: the first one whose source range holds
+    /// the value.
+    fn lookup(entries: &[(u64, u64, u64)], value: u64) -> u64 {
+        return entries
+            .iter()
+            .find(|(_, source, length)| (*source..source + length).contains(&value))
+            .map_or(value, |(destination, source, _)| destination + (value - source));
+    }
+
+    proptest! {
+        #[test]
+        fn lookup_matches_entries(entries in entries(), value in 0..300_u64) {
+            prop_assert_eq!(IntervalMap::from_entries(entries.clone()).get(value), lookup(&entries, value));
+        }
+
+        #[test]
+        fn composition_matches_sequential_application(first in entries(), second in entries(), value in 0..300_u64) {
+            let (first_map, second_map) = (IntervalMap::from_entries(first), IntervalMap::from_entries(second));
+
+            prop_assert_eq!(first_map.then(&second_map).get(value), second_map.get(first_map.get(value)));
+        }
+
+        #[test]
+        fn preimage_holds_exactly_the_values_mapping_into_range(entries in entries(), start in 0..250_u64, length in 0..50_u64) {
+            let map = IntervalMap::from_entries(entries);
+            let target = start..start + length;
+            let preimage: Vec<u64> = map.preimage(std::slice::from_ref(&target)).into_iter().flatten().filter(|value| *value < 400).collect();
+            let expected: Vec<u64> = (0..400).filter(|value| target.contains(&map.get(*value))).collect();
+
+            prop_assert_eq!(preimage, expected);
+        }
+
+        #[test]
+        fn mapped_ranges_hold_exactly_the_mapped_values(entries in entries(), start in 0..250_u64, length in 0..50_u64) {
+            let map = IntervalMap::from_entries(entries);
+            let mut mapped: Vec<u64> = map.map_range(start..start + length).into_iter().flatten().collect();
+            let mut expected: Vec<u64> = (start..start + length).map(|value| map.get(value)).collect();
+            mapped.sort();
+            expected.sort();
+
+            prop_assert_eq!(mapped, expected);
+        }
+
+        #[test]
+        fn sweep_matches_mapping_each_range(entries in entries(), ranges in prop::collection::vec((0..250_u64, 0..50_u64), 0..8)) {
+            let map = IntervalMap::from_entries(entries);
+            let ranges: Vec<Range<u64>> = ranges.into_iter().map(|(start, length)| start..start + length).collect();
+            let one_by_one: Vec<Range<u64>> = ranges.iter().flat_map(|range| map.map_range(range.clone())).collect();
+
+            prop_assert_eq!(map.map_ranges(&ranges), normalize_ranges(&one_by_one));
+        }
+    }
+}
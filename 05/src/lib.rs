@@ -1,295 +1,358 @@
+mod category_graph;
+mod interval_map;
+mod validation;
+
+use aoc_common::{
+    diagnostics::{self, Level},
+    parse::{labelled_integers, tuple},
+    Error, ParseError, SolveError, Solution,
+};
 use regex::Regex;
-use std::{collections::HashMap, ops::RangeInclusive, ops::Range};
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum MapItem {
-    Seed,
-    Soil,
-    Fertilizer,
-    Water,
-    Light,
-    Temperature,
-    Humidity,
-    Location,
-}
+use std::ops::Range;
 
-#[derive(Debug)]
-struct Map {
-    from: MapItem,
-    destination_range_start: u64,
-    source_range_start: u64,
-    range_length: u64
-}
+pub use category_graph::{CategoryError, CategoryGraph, Edge};
+pub use interval_map::{normalize_ranges, IntervalMap, Segment};
+pub use validation::{validate, Issue, IssueKind};
 
 #[derive(Debug, Clone)]
 struct MapEntry {
     destination_range_start: u64,
     source_range_start: u64,
-    range_length: u64
+    range_length: u64,
+    line_index: usize,
+    text: String,
 }
 
-
-fn get_map_item(map_item_str: &str) -> Result<MapItem, String> {
-    match map_item_str {
-        "seed" => return Ok(MapItem::Seed),
-        "soil" => return Ok(MapItem::Soil),
-        "fertilizer" => return Ok(MapItem::Fertilizer),
-        "water" => return Ok(MapItem::Water),
-        "light" => return Ok(MapItem::Light),
-        "temperature" => return Ok(MapItem::Temperature),
-        "humidity" => return Ok(MapItem::Humidity),
-        "location" => return Ok(MapItem::Location),
-        _ => return Err(format!("could not match {} to MapItem", map_item_str)),
-    }
+/// One `<from>-to-<to> map:` block with its entries.
+#[derive(Debug)]
+struct Block {
+    from: String,
+    to: String,
+    line_index: usize,
+    header: String,
+    entries: Vec<MapEntry>,
 }
 
-fn get_map_data(input: &str) -> Vec<Map> {
-    let from_to_regex = Regex::new(r"(?<from>[a-z]+)-to-(?<to>[a-z]+) map:").unwrap();
-    let map_entry_regex = Regex::new(r"(?<destination_range_start>\d+) (?<source_range_start>\d+) (?<range_length>\d+)").unwrap();
-
-    let mut vec_vec_map_entries: Vec<Map> = vec![];
-    let mut from = MapItem::Seed;
-    for line in input.lines().skip(2) {
-        if let Some(map_title_match) = from_to_regex.captures(line) {
-            let from_str: &str = map_title_match["from"].into();
-            from = get_map_item(from_str).unwrap_or_else(|err| {
-                panic!("Error:{}", err);
-            });
-        } else if let Some(map_entry_match) = map_entry_regex.captures(line) {
-            let destination_range_start_str: &str = map_entry_match["destination_range_start"].into();
-            let source_range_start_str: &str = map_entry_match["source_range_start"].into();
-            let range_length_str: &str = map_entry_match["range_length"].into();
-
-            let destination_range_start: u64 = destination_range_start_str.parse().unwrap_or_else(|err| {
-                panic!("Error:{}", err);
-            });
-            let source_range_start: u64 = source_range_start_str.parse().unwrap_or_else(|err| {
-                panic!("Error:{}", err);
-            });
-            let range_length: u64 = range_length_str.parse().unwrap_or_else(|err| {
-                panic!("Error:{}", err);
-            });
+impl Block {
+    fn get_interval_map(&self) -> IntervalMap {
+        return IntervalMap::from_entries(
+            self.entries
+                .iter()
+                .map(|entry| (entry.destination_range_start, entry.source_range_start, entry.range_length)),
+        );
+    }
 
-            vec_vec_map_entries.push(Map { from: from.clone(), destination_range_start, source_range_start, range_length })
-        }
-        else {
-            if line.len() != 0 {
-                println!("So i didn't process this line: {:?}", line);
-            }
-        }
+    fn into_edge(self) -> Edge {
+        let map = self.get_interval_map();
+        return Edge { from: self.from, to: self.to, map, line_index: self.line_index, header: self.header };
     }
-    return vec_vec_map_entries;
 }
 
-fn get_seeds(seeds_line: &str) -> Vec<u64> {
-    let number_regex = Regex::new(r"\b(\d+)\b").unwrap();
-    return number_regex
-        .captures_iter(seeds_line)
-        .flat_map(|captures| captures.get(1))
-        .map(|number_str| number_str.as_str().parse::<u64>())
-        .filter_map(Result::ok)
-        .collect();
+fn is_map_entry_line(line: &str) -> bool {
+    return line.starts_with(|c: char| c.is_ascii_digit());
 }
 
-fn get_map_item_to_maps(vec_vec_map_entries: &Vec<Map>) -> HashMap::<MapItem, HashMap<RangeInclusive<u64>, u64>> {
-    let mut map_item_to_maps = HashMap::<MapItem, HashMap<RangeInclusive<u64>, u64>>::new();
-
-    for map_entry in vec_vec_map_entries.iter() {
-        if !map_item_to_maps.contains_key(&map_entry.from) {
-            map_item_to_maps.insert(map_entry.from.clone(), HashMap::new());
-        }
-        let Some(map_of_ranges_to_dest) = map_item_to_maps.get_mut(&map_entry.from) else {
-            panic!("No vector found for {:?}", map_entry.from);
-        };
-        let source_range_start = map_entry.source_range_start.clone();
-        let range_length = map_entry.range_length.clone();
-        let destination_range_start = map_entry.destination_range_start.clone();
-
-        map_of_ranges_to_dest.insert(
-            source_range_start..=(source_range_start + range_length - 1),
-            destination_range_start
-        );
-    }
+fn get_map_entry(line_index: usize, line: &str) -> Result<MapEntry, ParseError> {
+    let [destination_range_start, source_range_start, range_length] = tuple::<u64, 3>(line_index, line, line)?;
 
-    return map_item_to_maps;
+    return Ok(MapEntry { destination_range_start, source_range_start, range_length, line_index, text: line.to_string() });
 }
 
-fn get_next_map_item(map_item: &MapItem) -> Option<MapItem> {
-    match map_item {
-        MapItem::Seed => return Some(MapItem::Soil),
-        MapItem::Soil => return Some(MapItem::Fertilizer),
-        MapItem::Fertilizer => return Some(MapItem::Water),
-        MapItem::Water => return Some(MapItem::Light),
-        MapItem::Light => return Some(MapItem::Temperature),
-        MapItem::Temperature => return Some(MapItem::Humidity),
-        MapItem::Humidity => return Some(MapItem::Location),
-        MapItem::Location => return None,
-
-    }
-}
+fn get_blocks(input: &str) -> Result<Vec<Block>, ParseError> {
+    let from_to_regex = Regex::new(r"(?<from>[a-z]+)-to-(?<to>[a-z]+) map:").unwrap();
 
-fn get_location_value(seed: u64, map_item_to_maps: &HashMap::<MapItem, HashMap<RangeInclusive<u64>, u64>>) -> u64 {
-    let mut current_map_item = MapItem::Seed;
-    let mut current_value = seed;
-    while current_map_item != MapItem::Location {
-        let map_for_range_to_destination_value = map_item_to_maps.get(&current_map_item)
-            .unwrap_or_else(|| panic!("Vec of maps not found for {:?}", current_map_item));
-        let has_map: bool= map_for_range_to_destination_value
-            .keys()
-            .any(|key| {
-                return key.contains(&current_value);
+    let mut blocks: Vec<Block> = vec![];
+    for (line_index, line) in input.lines().enumerate().skip(2) {
+        if let Some(map_title_match) = from_to_regex.captures(line) {
+            blocks.push(Block {
+                from: map_title_match.name("from").unwrap().as_str().to_string(),
+                to: map_title_match.name("to").unwrap().as_str().to_string(),
+                line_index,
+                header: line.to_string(),
+                entries: vec![],
             });
-
-        if has_map {
-            let (range, destination) = map_for_range_to_destination_value
-                .iter()
-                .filter(|(range, _)| range.contains(&current_value))
-                .next()
-                .unwrap_or_else(|| panic!("Could not get the entry with current value in the range"));
-            let offset = current_value - range.start();
-            current_value = destination + offset;
+        } else if is_map_entry_line(line) {
+            let Some(block) = blocks.last_mut() else {
+                return Err(ParseError::in_line(line_index, line, line, "expected a `<from>-to-<to> map:` header before the first entry"));
+            };
+            block.entries.push(get_map_entry(line_index, line)?);
+        }
+        else if !line.trim().is_empty() {
+            diagnostics::warn(format_args!("line {}: ignoring unrecognized line {:?}", line_index + 1, line));
         }
-
-        current_map_item = get_next_map_item(&current_map_item).unwrap_or_else(|| panic!("Location maps to None"));
     }
-    return current_value;
+    return Ok(blocks);
 }
 
-fn get_min_location(input: &str, seeds: Vec<u64>) -> u64 {
-    let vec_vec_map_entries: Vec<Map> = get_map_data(input);
-    let map_item_to_maps = get_map_item_to_maps(&vec_vec_map_entries);
+fn get_seeds(seeds_line: &str) -> Result<Vec<u64>, ParseError> {
+    let seeds: Vec<u64> = labelled_integers(0, seeds_line, "seeds")?;
+    if seeds.is_empty() {
+        return Err(ParseError::in_line(0, seeds_line, seeds_line, "expected at least one seed"));
+    }
+    return Ok(seeds);
+}
 
-    return seeds
-        .into_iter()
-        .map(|seed| get_location_value(seed, &map_item_to_maps))
-        .min()
-        .unwrap_or_else(|| panic!("could not unwrap min"));
+/// Every category named in a block header, with the blocks as the maps between them.
+fn get_category_graph(input: &str) -> Result<CategoryGraph, ParseError> {
+    return CategoryGraph::new(get_blocks(input)?.into_iter().map(Block::into_edge));
 }
 
-fn get_seeds_line(input: &str) -> &str {
+fn get_seeds_line(input: &str) -> Result<&str, ParseError> {
     let Some(seeds_line) = input.lines().next() else {
-        panic!("Cannot get seeds line");
+        return Err(ParseError::new(1, 1, "", "almanac is empty, expected a `seeds:` line"));
     };
-    return seeds_line;
+    return Ok(seeds_line);
 }
 
-pub fn part1(input: &str) -> u64 {
-    let seeds: Vec<u64> = get_seeds(get_seeds_line(input));
-    return get_min_location(input, seeds);
+pub fn part1(input: &str) -> Result<u64, Error> {
+    return Ok(Day05::part1(&Day05::parse(input)?)?);
 }
 
-fn get_seeds_for_input_as_range(seeds_line: &str) -> Vec<Range<u64>> {
-    let all_seed_values = get_seeds(seeds_line);
+fn get_seeds_as_ranges(all_seed_values: &[u64]) -> Vec<Range<u64>> {
+    if let [.., last] = all_seed_values {
+        if !all_seed_values.len().is_multiple_of(2) {
+            diagnostics::warn(format_args!("seed {} has no range length and is ignored in part 2", last));
+        }
+    }
     let seed_start_range_length_pairs: Vec<(u64, u64)> = all_seed_values
-        .chunks(2)
+        .chunks_exact(2)
         .map(|chunk| (chunk[0], chunk[1]))
         .collect();
     return seed_start_range_length_pairs
         .iter()
-        .map(|(start, length)| (*start..(*start+*length)))
+        .map(|(start, length)| *start..start.saturating_add(*length))
         .collect();
 }
 
-fn get_range_maps(input: &str) -> Vec<Vec<MapEntry>> {
-    let from_to_regex = Regex::new(r"(?<from>[a-z]+)-to-(?<to>[a-z]+) map:").unwrap();
-    let map_entry_regex = Regex::new(r"(?<destination_range_start>\d+) (?<source_range_start>\d+) (?<range_length>\d+)").unwrap();
-
-    let mut vec_vec_map_entries: Vec<Vec<MapEntry>> = vec![];
-    let mut vec_of_map_entries: Vec<MapEntry> = vec![];
-    for line in input.lines().skip(2) {
-        if let Some(_) = from_to_regex.captures(line) {
-            vec_of_map_entries = vec![];
-        } else if let Some(map_entry_match) = map_entry_regex.captures(line) {
-            let destination_range_start_str: &str = map_entry_match["destination_range_start"].into();
-            let source_range_start_str: &str = map_entry_match["source_range_start"].into();
-            let range_length_str: &str = map_entry_match["range_length"].into();
-
-            let destination_range_start: u64 = destination_range_start_str.parse().unwrap_or_else(|err| {
-                panic!("Error:{}", err);
-            });
-            let source_range_start: u64 = source_range_start_str.parse().unwrap_or_else(|err| {
-                panic!("Error:{}", err);
-            });
-            let range_length: u64 = range_length_str.parse().unwrap_or_else(|err| {
-                panic!("Error:{}", err);
-            });
+pub fn part2(input: &str) -> Result<u64, Error> {
+    return Ok(Day05::part2(&Day05::parse(input)?)?);
+}
 
-            vec_of_map_entries.push(MapEntry { destination_range_start, source_range_start, range_length });
-        }
-        else {
-            if line.len() != 0 {
-                println!("So i didn't process this line: {:?}", line);
-            }
-            vec_vec_map_entries.push(vec_of_map_entries.clone());
-        }
+/// The parsed almanac: the seeds, the categories and the maps between them, and the maps from
+/// seed to location composed into one.
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    categories: CategoryGraph,
+    seed_to_location: IntervalMap,
+}
+
+impl Almanac {
+    pub fn categories(&self) -> &CategoryGraph {
+        return &self.categories;
+    }
+
+    /// The seed ranges whose locations fall in `location_ranges`.
+    pub fn get_seeds_for_locations(&self, location_ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        return self.categories
+            .get_preimage("seed", "location", location_ranges)
+            .unwrap_or_else(|err| panic!("seed to location was checked while parsing: {}", err));
     }
-    vec_vec_map_entries.push(vec_of_map_entries);
-    return vec_vec_map_entries;
 }
 
-pub fn part2(input: &str) -> u64 {
-    let mut input_ranges: Vec<Range<u64>> = get_seeds_for_input_as_range(get_seeds_line(input));
-    let blocks: Vec<Vec<MapEntry>> = get_range_maps(input);
-
-    for map_entries in blocks.into_iter() {
-        let mut mapped_input_ranges: Vec<Range<u64>> = vec![];
-        while input_ranges.len() != 0 {
-            let mut has_overlap = false;
-            let curr_input_range = input_ranges.remove(0);
-            for map_entry in map_entries.iter() {
-                let overlap_start = std::cmp::max(curr_input_range.start, map_entry.source_range_start);
-                let overlap_end = std::cmp::min(
-                    curr_input_range.end, map_entry.source_range_start + map_entry.range_length
-                );
-
-                if overlap_start < overlap_end {
-                    has_overlap = true;
-                    let mapped_range_start: u64 = overlap_start - map_entry.source_range_start +
-                        map_entry.destination_range_start;
-                    let mapped_range_end: u64 = overlap_end - map_entry.source_range_start +
-                        map_entry.destination_range_start;
-                    mapped_input_ranges.push(mapped_range_start..mapped_range_end);
-
-                    // push the ranges not in the overlap from the current range back into
-                    // input_ranges
-                    if curr_input_range.start < overlap_start {
-                        input_ranges.push(curr_input_range.start..overlap_start);
-                    }
-                    if curr_input_range.end > overlap_end {
-                        input_ranges.push(overlap_end..curr_input_range.end);
-                    }
-                    break;
-                }
-            }
-            if !has_overlap {
-                mapped_input_ranges.push(curr_input_range);
-            }
+/// Parses the almanac like [`Day05::parse`], but refuses one with any of the issues
+/// [`validate`] finds rather than settling overlaps by which entry comes first.
+pub fn parse_strict(input: &str) -> Result<Almanac, ParseError> {
+    // Parsing below reports the same unrecognized lines again, so keep them from validation quiet.
+    let (issues, _) = diagnostics::capture(|| validate(input));
+    if let Some(issue) = issues?.into_iter().next() {
+        return Err(issue.into());
+    }
+    return Day05::parse(input);
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Almanac, ParseError> {
+        let seeds_line: &str = get_seeds_line(input)?;
+        let seeds: Vec<u64> = get_seeds(seeds_line)?;
+        let categories: CategoryGraph = get_category_graph(input)?;
+        // The seeds are what has to reach a location, so a broken chain is reported against them.
+        let seed_to_location: IntervalMap = categories
+            .get_mapping("seed", "location")
+            .map_err(|err| ParseError::in_line(0, seeds_line, seeds_line, err.to_string()))?;
+        diagnostics::info(format_args!(
+            "{} seeds, {} categories, seed to location composed into {} segments",
+            seeds.len(),
+            categories.categories().count(),
+            seed_to_location.segments().len(),
+        ));
+        if diagnostics::is_enabled(Level::Debug) {
+            let path: Vec<&str> = categories.get_path("seed", "location").unwrap().iter().map(|edge| edge.to.as_str()).collect();
+            diagnostics::debug(format_args!("seed to location goes through seed -> {}", path.join(" -> ")));
         }
-        input_ranges = mapped_input_ranges;
+        return Ok(Almanac { seeds, categories, seed_to_location });
     }
 
-    return input_ranges
-        .iter()
-        .map(|range| range.start)
-        .min()
-        .unwrap_or_else(|| panic!(""));
+    fn part1(almanac: &Almanac) -> Result<u64, SolveError> {
+        return Ok(almanac.seeds
+            .iter()
+            .map(|seed| almanac.seed_to_location.get(*seed))
+            .min()
+            .unwrap());
+    }
+
+    /// Fails when the `seeds:` line holds no `start length` pair with a length above 0.
+    fn part2(almanac: &Almanac) -> Result<u64, SolveError> {
+        let seed_ranges: Vec<Range<u64>> = get_seeds_as_ranges(&almanac.seeds);
+        diagnostics::debug(format_args!("mapping {} seed ranges to locations", seed_ranges.len()));
+        return almanac.seed_to_location
+            .map_ranges(&seed_ranges)
+            .first()
+            .map(|range| range.start)
+            .ok_or_else(|| SolveError::new("no seed ranges: `seeds:` needs `start length` pairs with a length above 0"));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Pushes `value` through each map in turn.
+    fn get_location_value(value: u64, maps: &[&IntervalMap]) -> u64 {
+        return maps.iter().fold(value, |value, map| map.get(value));
+    }
+
     #[test]
     fn part1_example() {
-        let input = include_str!("../input.txt");
+        let input = include_str!("../test_input.txt");
 
-        assert_eq!(part1(input), 35);
+        assert_eq!(part1(input), Ok(35));
     }
 
     #[test]
     fn part2_example() {
-        let input = include_str!("../input.txt");
+        let input = include_str!("../test_input.txt");
+
+        assert_eq!(part2(input), Ok(46));
+    }
+
+    #[test]
+    fn composed_map_matches_sequential_maps() {
+        let almanac = Day05::parse(include_str!("../test_input.txt")).unwrap();
+        let path = almanac.categories().get_path("seed", "location").unwrap();
+        let maps: Vec<&IntervalMap> = path.iter().map(|edge| &edge.map).collect();
+
+        assert_eq!(maps.len(), 7);
+        for seed in 0..120 {
+            assert_eq!(almanac.seed_to_location.get(seed), get_location_value(seed, &maps));
+        }
+    }
+
+    #[test]
+    fn maps_between_any_reachable_categories() {
+        let almanac = Day05::parse(include_str!("../test_input.txt")).unwrap();
+        let categories = almanac.categories();
+        let soil_to_humidity = categories.get_mapping("soil", "humidity").unwrap();
+        let path: Vec<&str> = categories.get_path("soil", "humidity").unwrap().iter().map(|edge| edge.to.as_str()).collect();
+
+        assert_eq!(path, vec!["fertilizer", "water", "light", "temperature", "humidity"]);
+        // Seed 79 has soil 81 and humidity 78.
+        assert_eq!(soil_to_humidity.get(81), 78);
+        assert_eq!(
+            categories.get_mapping("humidity", "soil"),
+            Err(CategoryError::Unreachable { from: "humidity".to_string(), to: "soil".to_string() })
+        );
+        assert_eq!(categories.get_mapping("seed", "dirt"), Err(CategoryError::Unknown("dirt".to_string())));
+    }
+
+    #[test]
+    fn seeds_for_locations_round_trip() {
+        let almanac = Day05::parse(include_str!("../test_input.txt")).unwrap();
+        let path = almanac.categories().get_path("seed", "location").unwrap();
+        let maps: Vec<&IntervalMap> = path.iter().map(|edge| &edge.map).collect();
+
+        for location_range in [0..1, 35..47, 46..57, 60..100] {
+            let seed_ranges = almanac.get_seeds_for_locations(std::slice::from_ref(&location_range));
+            for seed in 0..200 {
+                let in_preimage = seed_ranges.iter().any(|range| range.contains(&seed));
+                assert_eq!(in_preimage, location_range.contains(&get_location_value(seed, &maps)), "seed {}", seed);
+            }
+        }
+        // The part 1 answer, location 35, comes from seed 13 alone.
+        assert_eq!(almanac.get_seeds_for_locations(std::slice::from_ref(&(35..36))), vec![13..14]);
+    }
+
+    #[test]
+    fn strict_parsing_refuses_overlaps() {
+        let input = include_str!("../test_input.txt").replace("52 50 48", "52 50 49");
+
+        assert_eq!(Day05::part1(&Day05::parse(&input).unwrap()), Ok(35));
+        let err = parse_strict(&input).unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (5, "seed-to-soil entry overlaps the entry on line 4"));
+        assert!(parse_strict(include_str!("../test_input.txt")).is_ok());
+    }
+
+    #[test]
+    fn unrecognized_lines_are_reported_not_printed() {
+        let input = include_str!("../test_input.txt").replace("seed-to-soil map:", "seed-to-soil map:\nnot an entry");
+
+        let (answer, diagnostics) = diagnostics::capture(|| part1(&input));
+        let warnings: Vec<&diagnostics::Diagnostic> = diagnostics.iter().filter(|diagnostic| diagnostic.level == Level::Warn).collect();
+        assert_eq!(answer, Ok(35));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "line 4: ignoring unrecognized line \"not an entry\"");
+    }
+
+    #[test]
+    fn part2_handles_large_almanacs() {
+        // 10007 is prime, so multiplying tile numbers by a different factor per block shuffles
+        // the tiles of every block.
+        let size: u64 = 10_007;
+        let seeds: Vec<String> = (0..size).map(|i| format!("{} {}", i * 997 % size * 1000 + i % 300, 700 + i % 500)).collect();
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+        for (block, (from, to)) in [("seed", "soil"), ("soil", "water"), ("water", "light"), ("light", "location")].iter().enumerate() {
+            input += &format!("\n{}-to-{} map:\n", from, to);
+            for tile in 0..size {
+                input += &format!("{} {} 1000\n", tile * (block as u64 + 2) % size * 1000, tile * 1000);
+            }
+        }
+
+        let almanac = Day05::parse(&input).unwrap();
+        let path = almanac.categories().get_path("seed", "location").unwrap();
+        let block_by_block = path
+            .iter()
+            .fold(get_seeds_as_ranges(&almanac.seeds), |ranges, edge| edge.map.map_ranges(&ranges));
+        assert_eq!(Day05::part2(&almanac), Ok(block_by_block[0].start));
+    }
+
+    #[test]
+    fn part2_without_seed_ranges_is_reported() {
+        let maps = "\n\nseed-to-location map:\n50 98 2\n";
+
+        for (seeds, lowest_seed_location) in [("seeds: 79", 79), ("seeds: 99 0 14 0", 0)] {
+            let almanac = Day05::parse(&format!("{}{}", seeds, maps)).unwrap();
+            assert_eq!(Day05::part1(&almanac), Ok(lowest_seed_location));
+            let err = Day05::part2(&almanac).unwrap_err();
+            assert_eq!(err.message, "no seed ranges: `seeds:` needs `start length` pairs with a length above 0");
+        }
+    }
+
+    #[test]
+    fn other_category_names_are_accepted() {
+        let input = "seeds: 1 2\n\nseed-to-dirt map:\n10 0 5\n\ndirt-to-location map:\n0 10 2\n";
+
+        assert_eq!(part1(input), Ok(1));
+    }
+
+    #[test]
+    fn missing_link_is_reported() {
+        let err = Day05::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nwater-to-location map:\n1 2 3\n").unwrap_err();
+
+        assert_eq!(err.message, "the almanac has no chain of maps from seed to location");
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 1, "seeds: 79 14"));
+    }
+
+    #[test]
+    fn cycle_is_reported() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-water map:\n1 2 3\n\nwater-to-soil map:\n1 2 3\n";
+        let err = Day05::parse(input).unwrap_err();
 
-        assert_eq!(part2(input), 46);
+        assert_eq!((err.line, err.message.as_str()), (9, "maps form a cycle: soil -> water -> soil"));
     }
 }
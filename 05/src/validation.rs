@@ -0,0 +1,157 @@
+use std::{collections::BTreeMap, fmt};
+
+use aoc_common::ParseError;
+
+use crate::{Block, MapEntry};
+
+/// What is wrong with a map entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The entry's source range shares values with the entry on `other_line` of the same block,
+    /// which is listed earlier and so takes precedence.
+    Overlap { other_line: usize },
+    ZeroLength,
+    /// The source or destination range runs past `u64::MAX`.
+    Overflow,
+}
+
+/// A suspicious entry in one of the almanac's map blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub from: String,
+    pub to: String,
+    /// 1-based line number of the entry.
+    pub line: usize,
+    pub text: String,
+    pub kind: IssueKind,
+}
+
+impl Issue {
+    /// What is wrong, without saying where.
+    pub fn get_problem(&self) -> String {
+        let problem = match self.kind {
+            IssueKind::Overlap { other_line } => format!("overlaps the entry on line {}", other_line),
+            IssueKind::ZeroLength => "has zero length".to_string(),
+            IssueKind::Overflow => format!("runs past {}", u64::MAX),
+        };
+        return format!("{}-to-{} entry {}", self.from, self.to, problem);
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} (at {:?})", self.line, self.get_problem(), self.text)
+    }
+}
+
+impl From<Issue> for ParseError {
+    fn from(issue: Issue) -> ParseError {
+        return ParseError::new(issue.line, 1, &issue.text, issue.get_problem());
+    }
+}
+
+fn get_issue(block: &Block, entry: &MapEntry, kind: IssueKind) -> Issue {
+    return Issue {
+        from: block.from.clone(),
+        to: block.to.clone(),
+        line: entry.line_index + 1,
+        text: entry.text.clone(),
+        kind,
+    };
+}
+
+/// The issues in one block, in line order. An entry overlapping earlier ones is reported once,
+/// against the earliest of them.
+fn get_block_issues(block: &Block) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = vec![];
+    for entry in block.entries.iter() {
+        if entry.range_length == 0 {
+            issues.push(get_issue(block, entry, IssueKind::ZeroLength));
+        } else if entry.source_range_start.checked_add(entry.range_length).is_none()
+            || entry.destination_range_start.checked_add(entry.range_length).is_none()
+        {
+            issues.push(get_issue(block, entry, IssueKind::Overflow));
+        }
+    }
+
+    // Which entry covers each value so far, as `start -> (end, entry)` for disjoint pieces. The
+    // earliest entry an entry overlaps is the one owning the earliest-listed piece it touches.
+    let mut covered: BTreeMap<u64, (u64, &MapEntry)> = BTreeMap::new();
+    for entry in block.entries.iter().filter(|entry| entry.range_length > 0) {
+        let start = entry.source_range_start;
+        let end = start.saturating_add(entry.range_length);
+        let first_start = covered.range(..=start).next_back().map_or(start, |(piece_start, _)| *piece_start);
+        let mut cursor = start;
+        let mut gaps: Vec<(u64, u64)> = vec![];
+        let mut earliest: Option<&MapEntry> = None;
+        for (piece_start, (piece_end, owner)) in covered.range(first_start..end) {
+            if *piece_end <= start {
+                continue;
+            }
+            if *piece_start > cursor {
+                gaps.push((cursor, *piece_start));
+            }
+            cursor = cursor.max(*piece_end);
+            if earliest.is_none_or(|earliest| owner.line_index < earliest.line_index) {
+                earliest = Some(owner);
+            }
+        }
+        if cursor < end {
+            gaps.push((cursor, end));
+        }
+        if let Some(earliest) = earliest {
+            issues.push(get_issue(block, entry, IssueKind::Overlap { other_line: earliest.line_index + 1 }));
+        }
+        for (gap_start, gap_end) in gaps {
+            covered.insert(gap_start, (gap_end, entry));
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    return issues;
+}
+
+/// Every overlapping, zero-length or overflowing entry in the almanac's map blocks.
+pub fn validate(input: &str) -> Result<Vec<Issue>, ParseError> {
+    return Ok(crate::get_blocks(input)?.iter().flat_map(get_block_issues).collect());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_is_clean() {
+        assert_eq!(validate(include_str!("../test_input.txt")), Ok(vec![]));
+    }
+
+    #[test]
+    fn reports_each_kind_with_line_numbers() {
+        let input = [
+            "seeds: 1 2",
+            "",
+            "seed-to-soil map:",
+            "50 98 2",
+            "10 0 5",
+            "52 99 3",
+            "0 20 0",
+            "0 18446744073709551615 2",
+            "",
+            "soil-to-location map:",
+            "0 0 10",
+            "100 5 10",
+        ]
+            .join("\n");
+
+        let issues: Vec<String> = validate(&input).unwrap().iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "line 6: seed-to-soil entry overlaps the entry on line 4 (at \"52 99 3\")",
+                "line 7: seed-to-soil entry has zero length (at \"0 20 0\")",
+                "line 8: seed-to-soil entry runs past 18446744073709551615 (at \"0 18446744073709551615 2\")",
+                "line 12: soil-to-location entry overlaps the entry on line 11 (at \"100 5 10\")",
+            ]
+        );
+    }
+}
@@ -1,6 +1,5 @@
-use core::time;
-
-use regex::Regex;
+use aoc_common::{parse::labelled, Error, ParseError, SolveError, Solution};
+use num_bigint::BigUint;
 
 #[derive(Debug)]
 struct RaceRecord {
@@ -8,84 +7,168 @@ struct RaceRecord {
     time: u64,
 }
 
-fn get_race_records(input: &str) -> Vec<RaceRecord> {
-    let lines: Vec<&str> = input.lines().collect();
-    let number_regex = Regex::new(r"\b(\d+)\b").unwrap();
-    let times: Vec<u64> = number_regex
-        .captures_iter(lines[0])
-        .flat_map(|captures| captures.get(1))
-        .map(|number_str| number_str.as_str().parse::<u64>())
-        .filter_map(Result::ok)
-        .collect();
-    let distances: Vec<u64> = number_regex
-        .captures_iter(lines[1])
-        .flat_map(|captures| captures.get(1))
-        .map(|number_str| number_str.as_str().parse::<u64>())
-        .filter_map(Result::ok)
-        .collect();
-
-    let mut race_records: Vec<RaceRecord> = vec![];
-    for i in 0..times.len() {
-        race_records.push(RaceRecord { distance: distances[i], time: times[i] });
-    }
-
-    return race_records;
+/// A race whose numbers may outgrow `u64`, as part 2's can with the digits of a whole line run
+/// together. Only then are they kept as big integers.
+#[derive(Debug)]
+enum LongRaceRecord {
+    Small(RaceRecord),
+    Big { distance: BigUint, time: BigUint },
 }
 
-fn get_race_record(input: &str) -> RaceRecord {
-    let lines: Vec<&str> = input.lines().collect();
-    let number_regex = Regex::new(r"\b(\d+)\b").unwrap();
-    let time_string: String = number_regex
-        .captures_iter(lines[0])
-        .flat_map(|captures| captures.get(1))
-        .map(|number_str| number_str.as_str())
-        .collect();
-    let distance_string: String = number_regex
-        .captures_iter(lines[1])
-        .flat_map(|captures| captures.get(1))
-        .map(|number_str| number_str.as_str())
-        .collect();
-
-    return RaceRecord {
-        distance: distance_string.parse::<u64>().unwrap_or_else(|err| panic!("{:?}", err)),
-        time: time_string.parse::<u64>().unwrap_or_else(|err| panic!("{:?}", err))
+impl LongRaceRecord {
+    fn new(distance: BigUint, time: BigUint) -> LongRaceRecord {
+        return match (u64::try_from(&distance), u64::try_from(&time)) {
+            (Ok(distance), Ok(time)) => LongRaceRecord::Small(RaceRecord { distance, time }),
+            _ => LongRaceRecord::Big { distance, time },
+        };
     }
+
+    fn get_num_ways_to_win(&self) -> BigUint {
+        return match self {
+            LongRaceRecord::Small(race_record) => BigUint::from(get_num_ways_to_beat_record(&race_record.distance, &race_record.time)),
+            LongRaceRecord::Big { distance, time } => get_num_ways_to_beat_long_record(distance, time),
+        };
+    }
+}
+
+/// `text`, a slice of the `line_index`th line `line`, as a number of any size.
+fn get_big_integer(line_index: usize, line: &str, text: &str) -> Result<BigUint, ParseError> {
+    return text
+        .parse::<BigUint>()
+        .map_err(|err| ParseError::in_line(line_index, line, text, err.to_string()));
+}
+
+/// The text following `label:` on the `line_index`th line of the race sheet.
+fn get_numbers_str<'a>(lines: &[&'a str], line_index: usize, label: &str) -> Result<&'a str, ParseError> {
+    let Some(line) = lines.get(line_index) else {
+        return Err(ParseError::new(line_index + 1, 1, "", format!("expected a `{}:` line", label)));
+    };
+    return labelled(line_index, line, label);
 }
 
-fn get_boat_distance(hold_time: &u64, time_given: &u64) -> u64 {
-    if hold_time >= time_given {
-        return 0;
+fn get_race_records(input: &str) -> Result<Vec<LongRaceRecord>, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let times_str: &str = get_numbers_str(&lines, 0, "Time")?;
+    let distances_str: &str = get_numbers_str(&lines, 1, "Distance")?;
+    let parse_column = |line_index: usize, numbers_str: &str| {
+        return numbers_str
+            .split_whitespace()
+            .map(|number_str| get_big_integer(line_index, lines[line_index], number_str))
+            .collect::<Result<Vec<BigUint>, ParseError>>();
+    };
+    let times: Vec<BigUint> = parse_column(0, times_str)?;
+    let distances: Vec<BigUint> = parse_column(1, distances_str)?;
+    if times.len() != distances.len() {
+        return Err(ParseError::in_line(
+            1,
+            lines[1],
+            lines[1],
+            format!("expected {} distances to match the times, found {}", times.len(), distances.len()),
+        ));
     }
 
-    return hold_time * (time_given - hold_time);
+    return Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| LongRaceRecord::new(distance, time))
+        .collect());
+}
+
+fn get_race_record(input: &str) -> Result<LongRaceRecord, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let parse_concatenated = |line_index: usize, label: &str| {
+        let numbers_str: &str = get_numbers_str(&lines, line_index, label)?;
+        let number_string: String = numbers_str.split_whitespace().collect();
+        return number_string
+            .parse::<BigUint>()
+            .map_err(|err| ParseError::in_line(line_index, lines[line_index], numbers_str, err.to_string()));
+    };
+    return Ok(LongRaceRecord::new(parse_concatenated(1, "Distance")?, parse_concatenated(0, "Time")?));
 }
 
+/// Holding for `h` of `time_given` ms beats the record when `h * (time_given - h) > record`.
+/// Writing `k = 2h - time_given`, that is `k² < time_given² - 4 * record`, so the winning `k` are
+/// the ones of the same parity as `time_given` strictly inside the square root of that
+/// discriminant. Everything is exact integer arithmetic in `u128`, so no rounding can let a hold
+/// time that only ties the record slip in.
 fn get_num_ways_to_beat_record(record_distance: &u64, time_given: &u64) -> u64 {
-    let mut i = 1;
-    let mut j = time_given - 1;
-    while get_boat_distance(&i, time_given) <= *record_distance {
-        i += 1;
+    let time_given = *time_given as u128;
+    let discriminant = match (time_given * time_given).checked_sub(4 * *record_distance as u128) {
+        Some(discriminant) if discriminant > 0 => discriminant,
+        _ => return 0,
+    };
+    let root = discriminant.isqrt();
+    // The largest |k| with k² strictly below the discriminant: an exact root only ties.
+    let mut max_offset = if root * root == discriminant { root - 1 } else { root };
+    if max_offset % 2 != time_given % 2 {
+        let Some(offset) = max_offset.checked_sub(1) else {
+            return 0;
+        };
+        max_offset = offset;
     }
+    // k runs over -max_offset, -max_offset + 2, ..., max_offset.
+    return (max_offset + 1) as u64;
+}
 
-    while get_boat_distance(&j, time_given) <= *record_distance {
-        j -= 1;
+/// [`get_num_ways_to_beat_record`] for numbers too big for `u64`.
+fn get_num_ways_to_beat_long_record(record_distance: &BigUint, time_given: &BigUint) -> BigUint {
+    let squared_time = time_given * time_given;
+    let four_records = record_distance * 4_u32;
+    if squared_time <= four_records {
+        return BigUint::ZERO;
+    }
+    let discriminant = squared_time - four_records;
+    let root = discriminant.sqrt();
+    let mut max_offset = if &root * &root == discriminant { root - 1_u32 } else { root };
+    if max_offset.bit(0) != time_given.bit(0) {
+        if max_offset == BigUint::ZERO {
+            return BigUint::ZERO;
+        }
+        max_offset -= 1_u32;
     }
+    return max_offset + 1_u32;
+}
 
-    return j - i + 1;
+pub fn part1(input: &str) -> Result<BigUint, Error> {
+    return Ok(Day06::part1(&Day06::parse(input)?)?);
 }
 
-pub fn part1(input: &str) -> u64 {
-    let race_records = get_race_records(input);
-    return race_records
-        .iter()
-        .map(|race_record| get_num_ways_to_beat_record(&race_record.distance, &race_record.time))
-        .product();
+pub fn part2(input: &str) -> Result<BigUint, Error> {
+    return Ok(Day06::part2(&Day06::parse(input)?)?);
 }
 
-pub fn part2(input: &str) -> u64 {
-    let race_record = get_race_record(input);
+/// Both readings of the race sheet: one race per column for part 1, and the single race
+/// formed by ignoring the spaces between digits for part 2.
+#[derive(Debug)]
+pub struct RaceSheet {
+    race_records: Vec<LongRaceRecord>,
+    race_record: LongRaceRecord,
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = RaceSheet;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
 
-    return get_num_ways_to_beat_record(&race_record.distance, &race_record.time);
+    fn parse(input: &str) -> Result<RaceSheet, ParseError> {
+        return Ok(RaceSheet {
+            race_records: get_race_records(input)?,
+            race_record: get_race_record(input)?,
+        });
+    }
+
+    fn part1(race_sheet: &RaceSheet) -> Result<BigUint, SolveError> {
+        return Ok(race_sheet.race_records
+            .iter()
+            .map(|race_record| race_record.get_num_ways_to_win())
+            .product());
+    }
+
+    fn part2(race_sheet: &RaceSheet) -> Result<BigUint, SolveError> {
+        return Ok(race_sheet.race_record.get_num_ways_to_win());
+    }
 }
 
 #[cfg(test)]
@@ -96,13 +179,105 @@ mod tests {
     fn part1_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part1(input), 288)
+        assert_eq!(part1(input), Ok(BigUint::from(288_u32)))
     }
 
     #[test]
     fn part2_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part2(input), 71503);
+        assert_eq!(part2(input), Ok(BigUint::from(71503_u32)));
+    }
+
+    fn get_boat_distance(hold_time: &u64, time_given: &u64) -> u64 {
+        if hold_time >= time_given {
+            return 0;
+        }
+
+        return hold_time * (time_given - hold_time);
+    }
+
+    /// The original solution, walking in from both ends; it only stops if the record can be beaten.
+    fn get_num_ways_by_walking(record_distance: &u64, time_given: &u64) -> u64 {
+        let mut i = 1;
+        let mut j = time_given - 1;
+        while get_boat_distance(&i, time_given) <= *record_distance {
+            i += 1;
+        }
+
+        while get_boat_distance(&j, time_given) <= *record_distance {
+            j -= 1;
+        }
+
+        return j - i + 1;
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force() {
+        for time_given in 0..=80_u64 {
+            for record_distance in 0..=time_given * time_given / 4 + 2 {
+                let num_ways = (0..=time_given)
+                    .filter(|hold_time| get_boat_distance(hold_time, &time_given) > record_distance)
+                    .count() as u64;
+
+                assert_eq!(get_num_ways_to_beat_record(&record_distance, &time_given), num_ways, "time {} record {}", time_given, record_distance);
+                if num_ways > 0 {
+                    assert_eq!(get_num_ways_by_walking(&record_distance, &time_given), num_ways);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ties_with_the_record_do_not_count() {
+        // Holding 10 or 20 ms of 30 goes exactly 200 mm; 5 * 5 = 25 is the best over 10 ms.
+        assert_eq!(get_num_ways_to_beat_record(&200, &30), 9);
+        assert_eq!(get_num_ways_to_beat_record(&25, &10), 0);
+        assert_eq!(get_num_ways_to_beat_record(&0, &u64::MAX), u64::MAX - 1);
+    }
+
+    #[test]
+    fn big_path_matches_u64_path() {
+        for time_given in 0..=80_u64 {
+            for record_distance in 0..=time_given * time_given / 4 + 2 {
+                assert_eq!(
+                    get_num_ways_to_beat_long_record(&BigUint::from(record_distance), &BigUint::from(time_given)),
+                    BigUint::from(get_num_ways_to_beat_record(&record_distance, &time_given)),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn part2_falls_back_to_big_integers() {
+        // Holding for 10^20 ms of the 30-digit time exactly ties the record, so the winning hold
+        // times are the ones strictly between 10^20 and time - 10^20.
+        let input = [
+            "Time:      12 3456789012345678 901234567890",
+            "Distance:  1234567889123456789 0123456789000000000 000000000000",
+        ]
+            .join("\n");
+
+        let race_sheet = Day06::parse(&input).unwrap();
+        assert!(matches!(race_sheet.race_record, LongRaceRecord::Big { .. }));
+        assert_eq!(Day06::part2(&race_sheet).map(|num_ways| num_ways.to_string()), Ok("123456788812345678901234567889".to_string()));
+        // 12 ms can never cover the first race's distance.
+        assert_eq!(Day06::part1(&race_sheet), Ok(BigUint::ZERO));
+    }
+
+    #[test]
+    fn part2_near_the_best_hold_time() {
+        let input = format!("Time: {}\nDistance: {}", "10000000000000000000000000000000000000007", "25000000000000000000000000000000000000034999999999999999999999999999999999987667");
+
+        assert_eq!(part2(&input), Ok(BigUint::from(222_u32)));
+        // The sheet is a single race, so part 1 is the same race read the same way.
+        assert_eq!(part1(&input), Ok(BigUint::from(222_u32)));
+    }
+
+    #[test]
+    fn missing_distance_line_is_reported() {
+        let err = Day06::parse("Time:      7  15   30\n").unwrap_err();
+
+        assert_eq!(err.line, 2);
     }
 }
@@ -1,6 +1,8 @@
 use::std::collections::HashMap;
-#[derive(Debug, Eq, PartialEq)]
-struct CamelHand {
+use::aoc_common::{Error, ParseError, SolveError, Solution};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CamelHand {
     bid: u32,
     hand: Vec<char>,
 }
@@ -49,7 +51,7 @@ fn get_hand_strength(hand: &Vec<char>) -> u32 {
         }
     }
 
-    let mut matches: Vec<u32> = card_counts.iter().map(|(_, count)| *count).collect();
+    let mut matches: Vec<u32> = card_counts.values().copied().collect();
     matches.sort();
     matches.reverse();
     match matches[0] {
@@ -106,30 +108,71 @@ impl PartialOrd for CamelHand{
     }
 }
 
-fn get_camel_hands(input: &str) -> Vec<CamelHand> {
+const CARDS: &str = "J23456789TQKA";
+const HAND_SIZE: usize = 5;
+
+fn get_camel_hand(line_index: usize, line: &str) -> Result<CamelHand, ParseError> {
+    let Some((hand_str, bid_str)) = line.split_once(' ') else {
+        return Err(ParseError::in_line(line_index, line, line, "expected a hand and a bid separated by a space"));
+    };
+    if hand_str.chars().count() != HAND_SIZE {
+        return Err(ParseError::in_line(line_index, line, hand_str, format!("expected {} cards", HAND_SIZE)));
+    }
+    if let Some((offset, card)) = hand_str.char_indices().find(|(_, card)| !CARDS.contains(*card)) {
+        return Err(ParseError::in_line(
+            line_index,
+            line,
+            &hand_str[offset..offset + card.len_utf8()],
+            format!("expected one of {}", CARDS),
+        ));
+    }
+    let hand: Vec<char> = hand_str.chars().collect();
+    let bid: u32 = bid_str
+        .parse::<u32>()
+        .map_err(|err| ParseError::in_line(line_index, line, bid_str, err.to_string()))?;
+    return Ok(CamelHand { hand, bid });
+}
+
+fn get_camel_hands(input: &str) -> Result<Vec<CamelHand>, ParseError> {
     return input
         .lines()
-        .map(|line| {
-            let hand_and_bid: Vec<String> = line.split(" ").take(2).map(|partition| partition.to_string()).collect();
-            let hand: Vec<char> = hand_and_bid[0].chars().collect();
-            let bid: u32 = hand_and_bid[1].parse().unwrap_or_else(|err| panic!("Err: {:?}", err));
-            return CamelHand { hand, bid };
-        })
+        .enumerate()
+        .map(|(line_index, line)| get_camel_hand(line_index, line))
         .collect();
 }
 
-pub fn part1(input: &str) -> u32 {
-    let mut camel_hands = get_camel_hands(input);
-    camel_hands.sort();
-    return camel_hands
-        .iter()
-        .enumerate()
-        .map(|(i, camel_hand)| (i as u32 +1) * camel_hand.bid)
-        .sum();
+pub fn part1(input: &str) -> Result<u32, Error> {
+    return Ok(Day07::part1(&Day07::parse(input)?)?);
 }
 
-pub fn part2(input: &str) -> u32 {
-    return 0;
+pub fn part2(input: &str) -> Result<u32, Error> {
+    return Ok(Day07::part2(&Day07::parse(input)?)?);
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<CamelHand>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<CamelHand>, ParseError> {
+        return get_camel_hands(input);
+    }
+
+    fn part1(camel_hands: &Vec<CamelHand>) -> Result<u32, SolveError> {
+        let mut camel_hands = camel_hands.clone();
+        camel_hands.sort();
+        return Ok(camel_hands
+            .iter()
+            .enumerate()
+            .map(|(i, camel_hand)| (i as u32 +1) * camel_hand.bid)
+            .sum());
+    }
+
+    fn part2(_camel_hands: &Vec<CamelHand>) -> Result<u32, SolveError> {
+        return Ok(0);
+    }
 }
 
 #[cfg(test)]
@@ -150,6 +193,13 @@ mod tests {
     //     assert_eq!(part2(input), 5905);
     // }
 
+    #[test]
+    fn unknown_card_is_reported() {
+        let err = Day07::parse("32T3K 765\nT55X5 684").unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 4, "X"));
+    }
+
     #[test]
     fn test_hand_strenth() {
         assert_eq!(get_hand_strength(&vec![
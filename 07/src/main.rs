@@ -1,6 +0,0 @@
-use day_07::part1;
-
-fn main() {
-    let input = include_str!("../input.txt");
-    println!("{:?}", part1(input));
-}
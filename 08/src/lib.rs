@@ -1,6 +1,6 @@
 use::std::collections::HashMap;
-use::num::integer::lcm;
 use::regex::Regex;
+use::aoc_common::{diagnostics, Error, ParseError, SolveError, Solution};
 
 #[derive(Debug)]
 struct Node {
@@ -8,140 +8,141 @@ struct Node {
     right: String,
 }
 
-fn get_instructions(input: &str) -> Vec<char> {
-   let first_line: String = input.lines().take(1).collect();
-    return first_line.chars().collect();
+fn get_instructions(input: &str) -> Result<Vec<char>, ParseError> {
+    let first_line: &str = input.lines().next().unwrap_or("");
+    if first_line.is_empty() {
+        return Err(ParseError::new(1, 1, first_line, "expected a line of L/R instructions"));
+    }
+    if let Some((offset, direction)) = first_line.char_indices().find(|(_, c)| *c != 'L' && *c != 'R') {
+        return Err(ParseError::in_line(
+            0,
+            first_line,
+            &first_line[offset..offset + direction.len_utf8()],
+            "expected an L or R instruction",
+        ));
+    }
+    return Ok(first_line.chars().collect());
 }
 
-fn get_node_map(input: &str) -> HashMap<String, Node> {
+fn get_node_map(input: &str) -> Result<HashMap<String, Node>, ParseError> {
     let mut node_map: HashMap<String, Node> = HashMap::new();
     let node_regex = Regex::new(
-        r"(?<node_name>[A-Z]{3,3}) = \((?<left_node>[A-Z]{3,3}), (?<right_node>[A-Z]{3,3})\)"
+        r"^(?<node_name>[0-9A-Z]{3}) = \((?<left_node>[0-9A-Z]{3}), (?<right_node>[0-9A-Z]{3})\)$"
     ).unwrap();
 
-    for line in input.lines().skip(2) {
+    let lines: Vec<(usize, &str)> = input.lines().enumerate().skip(2).collect();
+    for (line_index, line) in lines.iter() {
         if let Some(line_cap) = node_regex.captures(line) {
             let node_name = line_cap["node_name"].to_string();
             let left = line_cap["left_node"].to_string();
             let right = line_cap["right_node"].to_string();
-            node_map.insert(node_name, Node {left, right});
+            if node_map.insert(node_name, Node {left, right}).is_some() {
+                let node_name_str = line_cap.name("node_name").unwrap().as_str();
+                return Err(ParseError::in_line(*line_index, line, node_name_str, "node is defined twice"));
+            }
         } else {
-            panic!("paniced at line: {:?}", line);
+            return Err(ParseError::in_line(*line_index, line, line, "expected `AAA = (BBB, CCC)`"));
         }
     }
 
-    return node_map;
-}
-
-fn get_num_steps_to_reach_zzz(instructions: Vec<char>, node_map: HashMap<String, Node>) -> u32 {
-    let mut i = 0;
-    let mut num_steps = 0;
-    let mut current_node = "AAA".to_string();
-    while current_node != "ZZZ" {
-        let direction = instructions[i];
-        let Some(node) = node_map.get(&current_node) else {
-            panic!("{:?} not in map", current_node);
-        };
-        
-        if direction == 'L' {
-            current_node = node.left.clone();
-        } else if direction == 'R' {
-            current_node = node.right.clone();
-        } else {
-            panic!("Direction is not L or R, it is: {:?}", direction);
+    // Every node we can step to has to be defined, so walking the network can't get stuck.
+    for (line_index, line) in lines.iter() {
+        let line_cap = node_regex.captures(line).unwrap();
+        for next_node in ["left_node", "right_node"].map(|name| line_cap.name(name).unwrap()) {
+            if !node_map.contains_key(next_node.as_str()) {
+                return Err(ParseError::in_line(*line_index, line, next_node.as_str(), "node is never defined"));
+            }
         }
-        i = (i + 1) % instructions.len();
-        num_steps += 1;
     }
-    return num_steps;
+
+    return Ok(node_map);
+}
+
+fn get_endless_walk_error(start: &str) -> SolveError {
+    return SolveError::new(format!("the walk from {} never reaches an end node", start));
 }
-fn get_num_steps_to_reach_zzz_with_node(instructions: &Vec<char>, node_map: &HashMap<String, Node>, node: &String) -> u64 {
+
+/// Steps from `start` until `is_end` holds, or `None` if the walk goes round in circles. After
+/// as many steps as there are (node, instruction) pairs, some pair has come up twice, and from
+/// then on the walk repeats itself.
+fn get_num_steps_to_reach(instructions: &[char], node_map: &HashMap<String, Node>, start: &str, is_end: impl Fn(&str) -> bool) -> Option<u64> {
+    let max_steps = (node_map.len() * instructions.len()) as u64;
     let mut i = 0;
     let mut num_steps: u64 = 0;
-    let mut current_node = node.clone();
-    while current_node.get(2..3).unwrap() != "Z" {
-        let direction = instructions[i];
-        let Some(node) = node_map.get(&current_node) else {
-            panic!("{:?} not in map", current_node);
-        };
-        
-        if direction == 'L' {
-            current_node = node.left.clone();
-        } else if direction == 'R' {
-            current_node = node.right.clone();
-        } else {
-            panic!("Direction is not L or R, it is: {:?}", direction);
+    let mut current_node = start;
+    while !is_end(current_node) {
+        if num_steps > max_steps {
+            return None;
         }
+        let node = &node_map[current_node];
+        current_node = if instructions[i] == 'L' { &node.left } else { &node.right };
         i = (i + 1) % instructions.len();
         num_steps += 1;
     }
-    return num_steps;
+    return Some(num_steps);
 }
 
-fn get_num_steps_for_all_starting_nodes_to_reach_zzz(instructions: Vec<char>, node_map: HashMap<String, Node>) -> u32 {
-    let mut i = 0;
-    let mut num_steps = 0;
-
-    let mut current_nodes: Vec<String> = node_map
-        .keys()
-        .filter(|key| key.get(2..3).unwrap() == "A")
-        .map(|key| key.to_string())
-        .collect();
-
-    println!("{:?}", current_nodes);
-
-    while !current_nodes.iter().all(|node| node.get(2..3).unwrap() == "Z")  {
-        let direction = instructions[i];
-        for j in 0..current_nodes.len() {
-            let Some(node) = node_map.get(&current_nodes[j]) else {
-                panic!("{:?} not in map", current_nodes[j]);
-            };
-
-            if direction == 'L' {
-                current_nodes[j] = node.left.clone();
-            } else if direction == 'R' {
-                current_nodes[j] = node.right.clone();
-            } else {
-                panic!("Direction is not L or R, it is: {:?}", direction);
-            }
-        }
-        i = (i + 1) % instructions.len();
-        num_steps += 1;
-    }
-    return num_steps;
+pub fn part1(input: &str) -> Result<u64, Error> {
+    return Ok(Day08::part1(&Day08::parse(input)?)?);
 }
 
-pub fn part1(input: &str) -> u32 {
-    let instructions = get_instructions(input);
-    let node_map = get_node_map(input);
-    return get_num_steps_to_reach_zzz(instructions, node_map);
+pub fn part2(input: &str) -> Result<u64, Error> {
+    return Ok(Day08::part2(&Day08::parse(input)?)?);
+}
+fn find_lcm(numbers: &[u64]) -> u64 {
+    return numbers.iter().fold(1, |result, num| num::integer::lcm(result, *num));
 }
 
-pub fn part2(input: &str) -> u64{
-    let instructions = get_instructions(input);
-    let node_map = get_node_map(input);
-    let current_nodes: Vec<String> = node_map
-        .keys()
-        .filter(|key| key.get(2..3).unwrap() == "A")
-        .map(|key| key.to_string())
-        .collect();
-     let num_steps_to_reach_z: Vec<u64> = current_nodes
-        .iter()
-        .map(|node| get_num_steps_to_reach_zzz_with_node(&instructions, &node_map, node))
-        .collect();
-    return find_lcm(num_steps_to_reach_z);
+#[derive(Debug)]
+pub struct Network {
+    instructions: Vec<char>,
+    node_map: HashMap<String, Node>,
 }
-fn find_lcm(numbers: Vec<u64>) -> u64 {
-    if numbers.is_empty() {
-        return 0; // LCM is undefined for an empty list
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Network;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Network, ParseError> {
+        let network = Network {
+            instructions: get_instructions(input)?,
+            node_map: get_node_map(input)?,
+        };
+        diagnostics::info(format_args!("{} instructions, {} nodes", network.instructions.len(), network.node_map.len()));
+        return Ok(network);
     }
 
-    let mut result = numbers[0];
-    for &num in &numbers[1..] {
-        result = num::integer::lcm(result, num);
+    fn part1(network: &Network) -> Result<u64, SolveError> {
+        if !network.node_map.contains_key("AAA") {
+            return Err(SolveError::new("the network has no AAA node to start from"));
+        }
+        return get_num_steps_to_reach(&network.instructions, &network.node_map, "AAA", |node| node == "ZZZ")
+            .ok_or_else(|| get_endless_walk_error("AAA"));
     }
 
-    return result;
+    fn part2(network: &Network) -> Result<u64, SolveError> {
+        let mut current_nodes: Vec<&String> = network.node_map
+            .keys()
+            .filter(|key| key.ends_with('A'))
+            .collect();
+        if current_nodes.is_empty() {
+            return Err(SolveError::new("the network has no `..A` node to start from"));
+        }
+        current_nodes.sort();
+        diagnostics::debug(format_args!("walking from {} start nodes", current_nodes.len()));
+        let mut num_steps_to_reach_z: Vec<u64> = vec![];
+        for node in current_nodes {
+            let Some(num_steps) = get_num_steps_to_reach(&network.instructions, &network.node_map, node, |node| node.ends_with('Z')) else {
+                return Err(get_endless_walk_error(node));
+            };
+            diagnostics::debug(format_args!("{} reaches an end node after {} steps", node, num_steps));
+            num_steps_to_reach_z.push(num_steps);
+        }
+        return Ok(find_lcm(&num_steps_to_reach_z));
+    }
 }
 
 #[cfg(test)]
@@ -152,13 +153,51 @@ mod tests {
     fn part1_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part1(input), 0)
+        assert_eq!(part1(input), Ok(6))
     }
 
     #[test]
     fn part2_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part2(input), 0);
+        assert_eq!(part2(input), Ok(6));
+    }
+
+    #[test]
+    fn progress_is_reported() {
+        let input = include_str!("../test_input.txt");
+
+        let (answer, diagnostics) = diagnostics::capture(|| part2(input));
+        let messages: Vec<String> = diagnostics.iter().map(|diagnostic| diagnostic.to_string()).collect();
+        assert_eq!(answer, Ok(6));
+        assert_eq!(
+            messages,
+            vec!["info: 3 instructions, 3 nodes", "debug: walking from 1 start nodes", "debug: AAA reaches an end node after 6 steps"]
+        );
+    }
+
+    #[test]
+    fn missing_start_is_reported() {
+        let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)\n";
+
+        assert_eq!(part1(input), Err(Error::Solve(SolveError::new("the network has no AAA node to start from"))));
+        assert_eq!(part2(input), Ok(2));
+        assert_eq!(part2("L\n\nBBB = (BBB, BBB)\n"), Err(Error::Solve(SolveError::new("the network has no `..A` node to start from"))));
+    }
+
+    #[test]
+    fn unreachable_end_is_reported() {
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+
+        let endless = Err(Error::Solve(SolveError::new("the walk from AAA never reaches an end node")));
+        assert_eq!(part1(input), endless);
+        assert_eq!(part2(input), endless);
+    }
+
+    #[test]
+    fn undefined_node_is_reported() {
+        let err = Day08::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\n").unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 13, "ZZZ"));
     }
 }
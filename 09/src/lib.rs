@@ -1,25 +1,37 @@
-use::regex::Regex;
+use::aoc_common::{parse::integers, Error, ParseError, SolveError, Solution};
 
-fn get_sequences(input: &str) -> Vec<Vec<i32>> {
-    let number_regex = Regex::new(r"(-?\d+)").unwrap();
+/// A line of numbers whose differences, taken over and over, come down to all zeros, so it can
+/// be extrapolated either way.
+fn get_sequence(line_index: usize, line: &str) -> Result<Vec<i32>, ParseError> {
+    let sequence: Vec<i32> = integers(line_index, line, line)?;
+    if sequence.is_empty() {
+        return Err(ParseError::in_line(line_index, line, line, "expected a sequence of numbers"));
+    }
+
+    let mut diff_sequence: Vec<i32> = sequence.clone();
+    while !diff_sequence.iter().all(|element| *element == 0) {
+        if diff_sequence.len() == 1 {
+            return Err(ParseError::in_line(line_index, line, line, "the differences of this sequence never come down to all zeros"));
+        }
+        let Some(next_diff_sequence) = diff_sequence.windows(2).map(|pair| pair[1].checked_sub(pair[0])).collect() else {
+            return Err(ParseError::in_line(line_index, line, line, "the differences of this sequence do not fit in 32 bits"));
+        };
+        diff_sequence = next_diff_sequence;
+    }
+    return Ok(sequence);
+}
+
+fn get_sequences(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
      return input
         .lines()
-        .map(|line| {
-            let sequence: Vec<i32> = number_regex
-                .captures_iter(line)
-                .flat_map(|captures| captures.get(1))
-                .map(|number_str| number_str.as_str().parse::<i32>())
-                .filter_map(Result::ok)
-                .collect();
-            return sequence;
-        })
+        .enumerate()
+        .map(|(line_index, line)| get_sequence(line_index, line))
         .collect();
 }
 
-fn get_next_num_in_sequence(sequence: &Vec<i32>) -> i32 {
-    if sequence.len() == 0 {
-        panic!("sequence is empty");
-    }
+/// Every difference fits in an `i32`, as parsing checked, and there is one per row, so the
+/// extrapolated value fits in an `i64`.
+fn get_next_num_in_sequence(sequence: &[i32]) -> i64 {
     if sequence.iter().all(|element| *element == 0) {
         return 0;
     }
@@ -28,21 +40,15 @@ fn get_next_num_in_sequence(sequence: &Vec<i32>) -> i32 {
     for i in 0..(sequence.len() - 1) {
         diff_sequence.push(sequence[i + 1] - sequence[i]);
     }
-    return sequence[sequence.len() - 1] + get_next_num_in_sequence(&diff_sequence);
+    return sequence[sequence.len() - 1] as i64 + get_next_num_in_sequence(&diff_sequence);
 }
 
-pub fn part1(input: &str) -> i32 {
-    let sequences = get_sequences(input);
-    return sequences
-        .iter()
-        .map(|sequence| get_next_num_in_sequence(sequence))
-        .sum();
+pub fn part1(input: &str) -> Result<i64, Error> {
+    return Ok(Day09::part1(&Day09::parse(input)?)?);
 }
 
-fn get_prev_num_in_sequence(sequence: &Vec<i32>) -> i32 {
-    if sequence.len() == 0 {
-        panic!("sequence is empty");
-    }
+/// See [`get_next_num_in_sequence`].
+fn get_prev_num_in_sequence(sequence: &[i32]) -> i64 {
     if sequence.iter().all(|element| *element == 0) {
         return 0;
     }
@@ -51,15 +57,37 @@ fn get_prev_num_in_sequence(sequence: &Vec<i32>) -> i32 {
     for i in 0..(sequence.len() - 1) {
         diff_sequence.push(sequence[i + 1] - sequence[i]);
     }
-    return sequence[0] - get_prev_num_in_sequence(&diff_sequence);
+    return sequence[0] as i64 - get_prev_num_in_sequence(&diff_sequence);
 }
 
-pub fn part2(input: &str) -> i32 {
-    let sequences = get_sequences(input);
-    return sequences
-        .iter()
-        .map(|sequence| get_prev_num_in_sequence(sequence))
-        .sum();
+pub fn part2(input: &str) -> Result<i64, Error> {
+    return Ok(Day09::part2(&Day09::parse(input)?)?);
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Vec<i32>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+        return get_sequences(input);
+    }
+
+    fn part1(sequences: &Vec<Vec<i32>>) -> Result<i64, SolveError> {
+        return Ok(sequences
+            .iter()
+            .map(|sequence| get_next_num_in_sequence(sequence))
+            .sum());
+    }
+
+    fn part2(sequences: &Vec<Vec<i32>>) -> Result<i64, SolveError> {
+        return Ok(sequences
+            .iter()
+            .map(|sequence| get_prev_num_in_sequence(sequence))
+            .sum());
+    }
 }
 
 #[cfg(test)]
@@ -70,13 +98,37 @@ mod tests {
     fn part1_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part1(input), 114)
+        assert_eq!(part1(input), Ok(114))
     }
 
     #[test]
     fn part2_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part2(input), 2);
+        assert_eq!(part2(input), Ok(2));
+    }
+
+    #[test]
+    fn empty_line_is_reported() {
+        let err = Day09::parse("0 3 6\n\n1 3 6").unwrap_err();
+
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn sequences_that_cannot_be_extrapolated_are_reported() {
+        let err = Day09::parse("0 3 6\n1 2 4").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "the differences of this sequence never come down to all zeros"));
+
+        let err = Day09::parse("-2147483648 2147483647").unwrap_err();
+        assert_eq!(err.message, "the differences of this sequence do not fit in 32 bits");
+    }
+
+    #[test]
+    fn extrapolation_beyond_i32_is_counted() {
+        let input = "0 1000000000 2000000000";
+
+        assert_eq!(part1(input), Ok(3_000_000_000));
+        assert_eq!(part2(input), Ok(-1_000_000_000));
     }
 }
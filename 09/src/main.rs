@@ -1,6 +0,0 @@
-use day_09::part2;
-
-fn main() {
-    let input = include_str!("../input.txt");
-    println!("{:?}", part2(input));
-}
@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use aoc::{
+    bench::{Stage, Timer},
+    days,
+};
+use aoc_common::input::{read_input, InputSource};
+use criterion::{criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion};
+
+struct CriterionTimer<'a, 'b> {
+    group: &'a mut BenchmarkGroup<'b, WallTime>,
+}
+
+impl Timer for CriterionTimer<'_, '_> {
+    fn time(&mut self, stage: Stage, routine: &mut dyn FnMut()) {
+        self.group.bench_function(stage.to_string(), |bencher| bencher.iter(&mut *routine));
+    }
+}
+
+/// Benchmarks every registered day that has an input in the workspace.
+fn bench_days(c: &mut Criterion) {
+    let inputs_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    for day in days::registered() {
+        let source = InputSource::for_day(&inputs_dir, day);
+        let Ok(input) = read_input(&source) else {
+            continue;
+        };
+        let mut group = c.benchmark_group(format!("day_{:02}", day));
+        group.sample_size(10);
+        let solution = days::get(day).unwrap();
+        (solution.bench)(&input, &mut CriterionTimer { group: &mut group })
+            .unwrap_or_else(|err| panic!("could not parse {}: {}", source, err));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
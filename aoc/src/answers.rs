@@ -0,0 +1,88 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Default location of the answers file, relative to the workspace root.
+pub const ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Accepted answers, keyed by day, then by the hash of the input they were accepted for, then
+/// by part.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<u8, BTreeMap<String, BTreeMap<u8, String>>>,
+}
+
+/// Stable 64-bit FNV-1a hash of a puzzle input, as hex. Unlike `DefaultHasher` this does not
+/// change between Rust releases, so it can be stored.
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    return format!("{:016x}", hash);
+}
+
+impl Answers {
+    /// Loads the answers file at `path`, or an empty set of answers if it does not exist yet.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(err) => return Err(format!("could not read {}: {}", path.display(), err)),
+        };
+        return serde_json::from_str(&contents).map_err(|err| format!("could not parse {}: {}", path.display(), err));
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self).map_err(|err| err.to_string())?;
+        return fs::write(path, contents + "\n").map_err(|err| format!("could not write {}: {}", path.display(), err));
+    }
+
+    /// The accepted answer to `part` of `day` for `input`, if one was recorded.
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        return self.days.get(&day)?.get(&input_hash(input))?.get(&part).map(|answer| answer.as_str());
+    }
+
+    /// Records `answer` as the accepted answer to `part` of `day` for `input`.
+    pub fn insert(&mut self, day: u8, part: u8, input: &str, answer: String) {
+        self.days
+            .entry(day)
+            .or_default()
+            .entry(input_hash(input))
+            .or_default()
+            .insert(part, answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_hash_is_stable() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn answers_are_keyed_by_input() {
+        let mut answers = Answers::default();
+        answers.insert(5, 1, "seeds: 1", "35".to_string());
+
+        assert_eq!(answers.get(5, 1, "seeds: 1"), Some("35"));
+        assert_eq!(answers.get(5, 2, "seeds: 1"), None);
+        assert_eq!(answers.get(5, 1, "seeds: 2"), None);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut answers = Answers::default();
+        answers.insert(3, 2, "467..114..", "467835".to_string());
+        let json = serde_json::to_string(&answers).unwrap();
+
+        assert_eq!(json, format!("{{\"3\":{{\"{}\":{{\"2\":\"467835\"}}}}}}", input_hash("467..114..")));
+        assert_eq!(serde_json::from_str::<Answers>(&json).unwrap(), answers);
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part1 => write!(f, "part1"),
+            Stage::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+/// Something that measures how long a stage of a solution takes, e.g. by running it repeatedly.
+pub trait Timer {
+    fn time(&mut self, stage: Stage, routine: &mut dyn FnMut());
+}
+
+/// Hands the parse step and both parts of `S` to `timer` one at a time. Both parts are timed
+/// against a single parse of `input`, so they measure only the part itself.
+pub fn time_stages<S: Solution>(input: &str, timer: &mut dyn Timer) -> Result<(), ParseError> {
+    let parsed = S::parse(input)?;
+    timer.time(Stage::Parse, &mut || {
+        let _ = black_box(S::parse(black_box(input)));
+    });
+    timer.time(Stage::Part1, &mut || {
+        let _ = black_box(S::part1(black_box(&parsed)));
+    });
+    timer.time(Stage::Part2, &mut || {
+        let _ = black_box(S::part2(black_box(&parsed)));
+    });
+    return Ok(());
+}
+
+/// Summary of the samples taken for one stage, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub stddev_ns: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut nanos: Vec<f64> = samples.iter().map(|sample| sample.as_nanos() as f64).collect();
+        nanos.sort_by(|a, b| a.total_cmp(b));
+        let runs = nanos.len();
+        if runs == 0 {
+            return Stats { runs, mean_ns: 0.0, median_ns: 0.0, stddev_ns: 0.0 };
+        }
+
+        let mean_ns = nanos.iter().sum::<f64>() / runs as f64;
+        let median_ns = match runs % 2 {
+            0 => (nanos[runs / 2 - 1] + nanos[runs / 2]) / 2.0,
+            _ => nanos[runs / 2],
+        };
+        let variance = nanos.iter().map(|ns| (ns - mean_ns).powi(2)).sum::<f64>() / runs as f64;
+        return Stats { runs, mean_ns, median_ns, stddev_ns: variance.sqrt() };
+    }
+}
+
+/// Times each stage a fixed number of times after one untimed warm-up run.
+pub struct RepeatTimer {
+    runs: usize,
+    pub stats: BTreeMap<Stage, Stats>,
+}
+
+impl RepeatTimer {
+    pub fn new(runs: usize) -> RepeatTimer {
+        return RepeatTimer { runs, stats: BTreeMap::new() };
+    }
+}
+
+impl Timer for RepeatTimer {
+    fn time(&mut self, stage: Stage, routine: &mut dyn FnMut()) {
+        routine();
+        let samples: Vec<Duration> = (0..self.runs)
+            .map(|_| {
+                let start = Instant::now();
+                routine();
+                return start.elapsed();
+            })
+            .collect();
+        self.stats.insert(stage, Stats::from_samples(&samples));
+    }
+}
+
+/// Stage timings for each benchmarked day, as saved to and loaded from a baseline file.
+pub type Report = BTreeMap<u8, BTreeMap<Stage, Stats>>;
+
+pub fn load_report(path: &Path) -> Result<Report, String> {
+    let contents = fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+    return serde_json::from_str(&contents).map_err(|err| format!("could not parse {}: {}", path.display(), err));
+}
+
+pub fn save_report(report: &Report, path: &Path) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(report).map_err(|err| err.to_string())?;
+    return fs::write(path, contents + "\n").map_err(|err| format!("could not write {}: {}", path.display(), err));
+}
+
+pub fn format_duration(ns: f64) -> String {
+    if ns >= 1e9 {
+        return format!("{:.2} s", ns / 1e9);
+    }
+    if ns >= 1e6 {
+        return format!("{:.2} ms", ns / 1e6);
+    }
+    if ns >= 1e3 {
+        return format!("{:.2} µs", ns / 1e3);
+    }
+    return format!("{:.0} ns", ns);
+}
+
+/// Formats `report` as a table, with the change in median against `baseline` where the baseline
+/// has the same day and stage.
+pub fn format_report(report: &Report, baseline: Option<&Report>) -> String {
+    let mut table = format!(
+        "{:<4}  {:<6}  {:>10}  {:>10}  {:>10}  {:>5}",
+        "Day", "Stage", "Mean", "Median", "Stddev", "Runs"
+    );
+    if baseline.is_some() {
+        table.push_str(&format!("  {:>8}", "Change"));
+    }
+    table.push('\n');
+
+    for (day, stages) in report.iter() {
+        for (stage, stats) in stages.iter() {
+            table.push_str(&format!(
+                "{:<4}  {:<6}  {:>10}  {:>10}  {:>10}  {:>5}",
+                format!("{:02}", day),
+                stage.to_string(),
+                format_duration(stats.mean_ns),
+                format_duration(stats.median_ns),
+                format_duration(stats.stddev_ns),
+                stats.runs,
+            ));
+            if let Some(baseline) = baseline {
+                let change = match baseline.get(day).and_then(|stages| stages.get(stage)) {
+                    Some(base) if base.median_ns > 0.0 => {
+                        format!("{:+.1}%", (stats.median_ns - base.median_ns) / base.median_ns * 100.0)
+                    }
+                    _ => "-".to_string(),
+                };
+                table.push_str(&format!("  {:>8}", change));
+            }
+            table.push('\n');
+        }
+    }
+    return table;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_summarise_samples() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].map(Duration::from_nanos).to_vec();
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.mean_ns, 2.5);
+        assert_eq!(stats.median_ns, 2.5);
+        assert_eq!(stats.stddev_ns, 1.25_f64.sqrt());
+    }
+
+    #[test]
+    fn repeat_timer_times_every_stage() {
+        let mut timer = RepeatTimer::new(3);
+        time_stages::<day_09::Day09>(include_str!("../../09/test_input.txt"), &mut timer).unwrap();
+
+        assert_eq!(timer.stats.keys().copied().collect::<Vec<Stage>>(), vec![Stage::Parse, Stage::Part1, Stage::Part2]);
+        assert!(timer.stats.values().all(|stats| stats.runs == 3));
+    }
+
+    #[test]
+    fn report_compares_medians_with_baseline() {
+        let stats = |median_ns: f64| Stats { runs: 1, mean_ns: median_ns, median_ns, stddev_ns: 0.0 };
+        let report: Report = BTreeMap::from([(5, BTreeMap::from([(Stage::Part2, stats(1500.0))]))]);
+        let baseline: Report = BTreeMap::from([(5, BTreeMap::from([(Stage::Part2, stats(2000.0))]))]);
+
+        let table = format_report(&report, Some(&baseline));
+
+        assert!(table.lines().nth(1).unwrap().starts_with("05    part2"));
+        assert!(table.lines().nth(1).unwrap().ends_with("-25.0%"));
+    }
+}
@@ -0,0 +1,151 @@
+use day_2::{GameCheck, GameStats, Summary};
+use num_bigint::BigUint;
+use serde_json::json;
+
+use crate::table::format_table;
+
+/// One row per game saying whether it was feasible and, if not, which reveal broke the limit,
+/// followed by the sum of the feasible game IDs.
+pub fn format_checks(checks: &[GameCheck]) -> String {
+    let rows: Vec<[String; 3]> = checks
+        .iter()
+        .map(|check| {
+            let (feasible, violation) = match &check.violation {
+                None => ("yes", "".to_string()),
+                Some(violation) => ("no", violation.to_string()),
+            };
+            return [check.game_id.to_string(), feasible.to_string(), violation];
+        })
+        .collect();
+    let feasible_id_sum: u64 = checks.iter().filter(|check| check.is_feasible()).map(|check| check.game_id as u64).sum();
+
+    let mut report = format_table(["Game", "Feasible", "Violation"], &rows);
+    report.push_str(&format!(
+        "{} of {} games feasible, ID sum {}\n",
+        checks.iter().filter(|check| check.is_feasible()).count(),
+        checks.len(),
+        feasible_id_sum
+    ));
+    return report;
+}
+
+/// One row per game followed by the aggregates over all games.
+pub fn format_report_table(stats: &[GameStats], summary: &Summary) -> String {
+    let rows: Vec<[String; 5]> = stats
+        .iter()
+        .map(|game_stats| {
+            return [
+                game_stats.game_id.to_string(),
+                game_stats.minimal_bag.to_string(),
+                game_stats.total_cubes.to_string(),
+                game_stats.num_reveals.to_string(),
+                game_stats.power.to_string(),
+            ];
+        })
+        .collect();
+
+    let mut report = format_table(["Game", "Minimal bag", "Cubes", "Reveals", "Power"], &rows);
+    report.push_str(&format!("games: {}\n", summary.num_games));
+    if let Some((color, count)) = &summary.most_constrained_color {
+        report.push_str(&format!("most constrained color: {} (needs {})\n", color, count));
+    }
+    report.push_str(&format!(
+        "power: min {}, median {}, mean {:.1}, max {}\n",
+        summary.min_power, summary.median_power, summary.mean_power, summary.max_power
+    ));
+    return report;
+}
+
+/// A power as a JSON number, or as a decimal string once it no longer fits in a `u64`.
+fn get_power_json(power: &BigUint) -> serde_json::Value {
+    return match u64::try_from(power) {
+        Ok(power) => json!(power),
+        Err(_) => json!(power.to_string()),
+    };
+}
+
+/// The report as a single JSON document with a `games` array and a `summary` object.
+pub fn format_report_json(stats: &[GameStats], summary: &Summary) -> String {
+    let games: Vec<serde_json::Value> = stats
+        .iter()
+        .map(|game_stats| {
+            let minimal_bag: serde_json::Map<String, serde_json::Value> =
+                game_stats.minimal_bag.colors().map(|(color, count)| (color.to_string(), json!(count))).collect();
+            return json!({
+                "game": game_stats.game_id,
+                "minimal_bag": minimal_bag,
+                "total_cubes": game_stats.total_cubes,
+                "reveals": game_stats.num_reveals,
+                "power": get_power_json(&game_stats.power),
+            });
+        })
+        .collect();
+    let most_constrained_color = summary
+        .most_constrained_color
+        .as_ref()
+        .map(|(color, count)| json!({ "color": color, "count": count }));
+    let report = json!({
+        "games": games,
+        "summary": {
+            "games": summary.num_games,
+            "most_constrained_color": most_constrained_color,
+            "power": {
+                "min": get_power_json(&summary.min_power),
+                "median": summary.median_power,
+                "mean": summary.mean_power,
+                "max": get_power_json(&summary.max_power),
+            },
+        },
+    });
+    return serde_json::to_string_pretty(&report).unwrap() + "\n";
+}
+
+#[cfg(test)]
+mod tests {
+    use day_2::{check_games, get_game_stats, get_summary, parse, Bag};
+
+    use super::*;
+
+    #[test]
+    fn formats_feasibility_table() {
+        let games = parse("Game 1: 3 blue, 4 red\nGame 2: 1 red; 20 red").unwrap();
+
+        assert_eq!(
+            format_checks(&check_games(&games, &Bag::default())),
+            "Game  Feasible  Violation\n\
+             1     yes\n\
+             2     no        reveal 2 showed 20 red but the bag holds 12\n\
+             1 of 2 games feasible, ID sum 1\n"
+        );
+    }
+
+    #[test]
+    fn formats_report() {
+        let games = parse("Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 2: 2 blue").unwrap();
+        let stats: Vec<GameStats> = games.iter().map(get_game_stats).collect();
+        let summary = get_summary(&stats);
+
+        assert_eq!(
+            format_report_table(&stats, &summary),
+            "Game  Minimal bag           Cubes  Reveals  Power\n\
+             1     blue=3,green=2,red=4  10     2        24\n\
+             2     blue=2                2      1        2\n\
+             games: 2\n\
+             most constrained color: red (needs 4)\n\
+             power: min 2, median 13, mean 13.0, max 24\n"
+        );
+        let report: serde_json::Value = serde_json::from_str(&format_report_json(&stats, &summary)).unwrap();
+        assert_eq!(report["games"][0]["minimal_bag"]["red"], 4);
+        assert_eq!(report["summary"]["most_constrained_color"]["color"], "red");
+        assert_eq!(report["games"][0]["power"], 24);
+    }
+
+    #[test]
+    fn powers_beyond_u64_are_json_strings() {
+        let games = parse("Game 1: 100000 a, 100000 b, 100000 c, 100000 d").unwrap();
+        let stats: Vec<GameStats> = games.iter().map(get_game_stats).collect();
+
+        let report: serde_json::Value = serde_json::from_str(&format_report_json(&stats, &get_summary(&stats))).unwrap();
+        assert_eq!(report["games"][0]["power"], "100000000000000000000");
+    }
+}
@@ -0,0 +1,74 @@
+use day_04::CardBreakdown;
+use serde_json::json;
+
+use crate::table::format_table;
+
+fn format_numbers(numbers: &[u32]) -> String {
+    return numbers.iter().map(|number| number.to_string()).collect::<Vec<String>>().join(" ");
+}
+
+/// One row per card followed by the totals for both parts.
+pub fn format_breakdown_table(breakdown: &[CardBreakdown]) -> String {
+    let rows: Vec<[String; 4]> = breakdown
+        .iter()
+        .map(|card| {
+            return [
+                card.card_num.to_string(),
+                format_numbers(&card.matched_numbers),
+                card.points.to_string(),
+                card.copies.to_string(),
+            ];
+        })
+        .collect();
+
+    let mut report = format_table(["Card", "Matched", "Points", "Copies"], &rows);
+    report.push_str(&format!(
+        "total points {}, total cards {}\n",
+        breakdown.iter().map(|card| card.points).sum::<u64>(),
+        breakdown.iter().map(|card| card.copies).sum::<u64>()
+    ));
+    return report;
+}
+
+/// One JSON object per card, one per line.
+pub fn format_breakdown_json_lines(breakdown: &[CardBreakdown]) -> String {
+    return breakdown
+        .iter()
+        .map(|card| {
+            let line = json!({
+                "card": card.card_num,
+                "matched_numbers": card.matched_numbers,
+                "points": card.points,
+                "copies": card.copies,
+            });
+            return line.to_string() + "\n";
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+    use day_04::{get_breakdown, Day04};
+
+    use super::*;
+
+    #[test]
+    fn formats_breakdown() {
+        let scratch_cards = Day04::parse("Card 1: 41 48 | 48 41\nCard 2: 1 2 | 3 4").unwrap();
+        let breakdown = get_breakdown(&scratch_cards).unwrap();
+
+        assert_eq!(
+            format_breakdown_table(&breakdown),
+            "Card  Matched  Points  Copies\n\
+             1     41 48    2       1\n\
+             2              0       2\n\
+             total points 2, total cards 3\n"
+        );
+        assert_eq!(
+            format_breakdown_json_lines(&breakdown),
+            "{\"card\":1,\"copies\":1,\"matched_numbers\":[41,48],\"points\":2}\n\
+             {\"card\":2,\"copies\":2,\"matched_numbers\":[],\"points\":0}\n"
+        );
+    }
+}
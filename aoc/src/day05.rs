@@ -0,0 +1,79 @@
+use std::ops::Range;
+
+use day_05::{IntervalMap, Issue};
+
+use crate::table::format_table;
+
+/// The shifted pieces of `map`, one row each; every other value maps to itself.
+pub fn format_segments(map: &IntervalMap) -> String {
+    let rows: Vec<[String; 2]> = map
+        .segments()
+        .iter()
+        .map(|segment| {
+            let destination_end = segment.destination + (segment.end - segment.start);
+            return [format!("{}..{}", segment.start, segment.end), format!("{}..{}", segment.destination, destination_end)];
+        })
+        .collect();
+    return format_table(["Source", "Destination"], &rows);
+}
+
+/// A `start..end` range of values, or a single value `n` meaning `n..n+1`.
+pub fn parse_range(text: &str) -> Result<Range<u64>, String> {
+    let parse = |number: &str| number.trim().parse::<u64>().map_err(|err| format!("invalid number `{}`: {}", number, err));
+    let range = match text.split_once("..") {
+        Some((start, end)) => parse(start)?..parse(end)?,
+        None => {
+            let value = parse(text)?;
+            value..value.checked_add(1).ok_or(format!("{} has no value after it", value))?
+        }
+    };
+    if range.is_empty() {
+        return Err(format!("{}..{} holds no values", range.start, range.end));
+    }
+    return Ok(range);
+}
+
+/// One `start..end` line per range.
+pub fn format_ranges(ranges: &[Range<u64>]) -> String {
+    return ranges.iter().map(|range| format!("{}..{}\n", range.start, range.end)).collect();
+}
+
+/// One row per issue with its line, or a note that there are none.
+pub fn format_issues(issues: &[Issue]) -> String {
+    if issues.is_empty() {
+        return "no issues found\n".to_string();
+    }
+    let rows: Vec<[String; 3]> = issues
+        .iter()
+        .map(|issue| [issue.line.to_string(), issue.text.clone(), issue.get_problem()])
+        .collect();
+    return format_table(["Line", "Entry", "Problem"], &rows);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_segments() {
+        let map = IntervalMap::from_entries([(50, 98, 2), (52, 50, 48)]);
+
+        assert_eq!(format_segments(&map), "Source   Destination\n50..98   52..100\n98..100  50..52\n");
+    }
+
+    #[test]
+    fn formats_issues() {
+        let issues = day_05::validate("seeds: 1 2\n\nseed-to-location map:\n5 0 10\n7 3 2\n").unwrap();
+
+        assert_eq!(format_issues(&issues), "Line  Entry  Problem\n5     7 3 2  seed-to-location entry overlaps the entry on line 4\n");
+        assert_eq!(format_issues(&[]), "no issues found\n");
+    }
+
+    #[test]
+    fn parses_ranges_and_single_values() {
+        assert_eq!(parse_range("46..57"), Ok(46..57));
+        assert_eq!(parse_range("35"), Ok(35..36));
+        assert!(parse_range("57..46").is_err());
+        assert!(parse_range("a..b").is_err());
+    }
+}
@@ -0,0 +1,46 @@
+use aoc_common::{solve, ParseError, Solution, Solved};
+
+use crate::bench::{time_stages, Timer};
+
+/// A registered day's solution, with its `Solution` type erased so days can be looked up at
+/// runtime.
+pub struct Day {
+    /// Runs `parts` against `input`, parsing it once, see [`aoc_common::solve`].
+    pub solve: fn(&[u8], &str) -> Solved,
+    /// Times each stage against `input`, see [`time_stages`].
+    pub bench: fn(&str, &mut dyn Timer) -> Result<(), ParseError>,
+}
+
+impl Day {
+    fn of<S: Solution>() -> Day {
+        return Day { solve: solve::<S>, bench: time_stages::<S> };
+    }
+}
+
+/// The solution registered for `day`, if any.
+pub fn get(day: u8) -> Option<Day> {
+    match day {
+        1 => return Some(Day::of::<day_01::Day01>()),
+        2 => return Some(Day::of::<day_2::Day02>()),
+        3 => return Some(Day::of::<day_3::Day03>()),
+        4 => return Some(Day::of::<day_04::Day04>()),
+        5 => return Some(Day::of::<day_05::Day05>()),
+        6 => return Some(Day::of::<day_06::Day06>()),
+        7 => return Some(Day::of::<day_07::Day07>()),
+        8 => return Some(Day::of::<day_08::Day08>()),
+        9 => return Some(Day::of::<day_09::Day09>()),
+        _ => return None,
+    }
+}
+
+/// Every day with a registered solution, in order.
+pub fn registered() -> Vec<u8> {
+    return (1..=25).filter(|day| get(*day).is_some()).collect();
+}
+
+/// Parses `input` once and runs each of `parts` of `day` on it, returning the formatted answers
+/// (`None` for a part that does not exist) or why the input could not be parsed, or `None` when
+/// no solution is registered for that day.
+pub fn run(day: u8, parts: &[u8], input: &str) -> Option<Solved> {
+    return Some((get(day)?.solve)(parts, input));
+}
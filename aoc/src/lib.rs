@@ -0,0 +1,9 @@
+pub mod answers;
+pub mod bench;
+pub mod day02;
+pub mod day04;
+pub mod day05;
+pub mod days;
+pub mod scaffold;
+pub mod table;
+pub mod verify;
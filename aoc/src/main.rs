@@ -0,0 +1,404 @@
+use std::{ops::Range, path::PathBuf, process::ExitCode};
+
+use aoc::{
+    answers::{Answers, ANSWERS_FILE_NAME},
+    bench::{self, RepeatTimer, Report},
+    day02, day04, day05, days, scaffold, verify,
+};
+use aoc_common::{
+    diagnostics::{self, Level},
+    input::{read_input, InputSource},
+    Solution,
+};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use day_04::Day04;
+use day_05::{Almanac, Day05};
+use day_2::Bag;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Print progress notes from the solutions to stderr; repeat for debugging detail
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+    /// Only print errors to stderr, hiding warnings about the input
+    #[arg(short, long, global = true)]
+    quiet: bool,
+}
+
+impl Cli {
+    /// The least important diagnostics to print, warnings unless asked otherwise.
+    fn get_max_level(&self) -> Level {
+        if self.quiet {
+            return Level::Error;
+        }
+        return match self.verbose {
+            0 => Level::Warn,
+            1 => Level::Info,
+            _ => Level::Debug,
+        };
+    }
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Puzzle input file, or `-` for stdin; defaults to `<inputs-dir>/<day>/input.txt`
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<InputSource>,
+    /// Directory holding the per-day input directories
+    #[arg(long, env = "AOC_INPUTS_DIR", default_value = ".")]
+    inputs_dir: PathBuf,
+}
+
+impl InputArgs {
+    /// Where to read `day`'s input from, and its contents.
+    fn read(self, day: u8) -> Result<(InputSource, String), String> {
+        let source = self.input.unwrap_or_else(|| InputSource::for_day(&self.inputs_dir, day));
+        let input = read_input(&source).map_err(|err| err.to_string())?;
+        return Ok((source, input));
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day's solution and print the answer
+    Run {
+        /// Day to run (1-25)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part to run; both parts are run when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Record the answers as accepted for this input
+        #[arg(long)]
+        accept: bool,
+        /// File of accepted answers
+        #[arg(long, default_value = ANSWERS_FILE_NAME)]
+        answers: PathBuf,
+    },
+    /// Check every registered day's answers against the accepted ones
+    Verify {
+        /// Only verify this day
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Directory holding the per-day input directories
+        #[arg(long, env = "AOC_INPUTS_DIR", default_value = ".")]
+        inputs_dir: PathBuf,
+        /// File of accepted answers
+        #[arg(long, default_value = ANSWERS_FILE_NAME)]
+        answers: PathBuf,
+    },
+    /// Time the parse step and both parts of every registered day
+    Bench {
+        /// Only benchmark this day
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Number of timed runs of each stage
+        #[arg(short, long, default_value_t = 10)]
+        runs: usize,
+        /// Directory holding the per-day input directories
+        #[arg(long, env = "AOC_INPUTS_DIR", default_value = ".")]
+        inputs_dir: PathBuf,
+        /// Save the timings as JSON to compare later runs against
+        #[arg(long, value_name = "PATH")]
+        save: Option<PathBuf>,
+        /// Compare against timings previously saved with --save
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+    },
+    /// Day 02 tools beyond the puzzle answers
+    Day02 {
+        #[command(subcommand)]
+        command: Day02Command,
+    },
+    /// Day 04 tools beyond the puzzle answers
+    Day04 {
+        #[command(subcommand)]
+        command: Day04Command,
+    },
+    /// Day 05 tools beyond the puzzle answers
+    Day05 {
+        #[command(subcommand)]
+        command: Day05Command,
+    },
+    /// Create a new day crate from `template/` and register it with the workspace and runner
+    New {
+        /// Day to create (1-25)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Root of the workspace
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Day02Command {
+    /// Check which games could have been played with a given bag
+    Feasible {
+        /// Cubes in the bag as `color=count,...`; colors left out have no cubes
+        #[arg(long, default_value_t = Bag::default())]
+        bag: Bag,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Per-game statistics (minimal bag, cubes seen, reveals, power) and aggregates
+    Report {
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum Day04Command {
+    /// Per-card breakdown: matched numbers, points and copies held at the end
+    Cards {
+        /// `json` prints one JSON object per card per line
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum Day05Command {
+    /// Map values from one category to another, or print the whole mapping
+    Map {
+        /// Category to map from
+        #[arg(long, default_value = "seed")]
+        from: String,
+        /// Category to map to
+        #[arg(long, default_value = "location")]
+        to: String,
+        /// Values to map; the mapping's shifted ranges are printed when omitted
+        values: Vec<u64>,
+        /// Refuse almanacs with overlapping, zero-length or overflowing entries
+        #[arg(long)]
+        strict: bool,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Find every value of an earlier category that ends up in the given ranges
+    Inverse {
+        /// Category the ranges are in
+        #[arg(long, default_value = "location")]
+        from: String,
+        /// Earlier category to trace the ranges back to
+        #[arg(long, default_value = "seed")]
+        to: String,
+        /// Ranges as `start..end` (end exclusive), or single values
+        #[arg(required = true, value_parser = day05::parse_range)]
+        ranges: Vec<Range<u64>>,
+        /// Refuse almanacs with overlapping, zero-length or overflowing entries
+        #[arg(long)]
+        strict: bool,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// List overlapping, zero-length and overflowing entries in the map blocks
+    Check {
+        /// Fail if any entry is listed
+        #[arg(long)]
+        strict: bool,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+}
+
+fn run(day: u8, part: Option<u8>, input: InputArgs, accept: bool, answers_path: PathBuf) -> Result<(), String> {
+    let (source, input) = input.read(day)?;
+    // Only accepting touches the answers file, so a broken one does not stop plain runs.
+    let mut answers: Option<Answers> = match accept {
+        true => Some(Answers::load(&answers_path)?),
+        false => None,
+    };
+
+    let parts: Vec<u8> = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let Some(result) = days::run(day, &parts, &input) else {
+        return Err(format!("no solution registered for day {}", day));
+    };
+    let answers_by_part = result.map_err(|err| format!("could not parse {}: {}", source, err))?;
+    let mut failures: Vec<String> = vec![];
+    for (part, answer) in parts.into_iter().zip(answers_by_part) {
+        match answer {
+            Ok(answer) => {
+                println!("Day {:02} part {}: {}", day, part, answer);
+                if let Some(answers) = answers.as_mut() {
+                    answers.insert(day, part, &input, answer);
+                }
+            }
+            Err(err) => failures.push(format!("day {} part {} has no answer for {}: {}", day, part, source, err)),
+        }
+    }
+    if !failures.is_empty() {
+        return Err(failures.join("; "));
+    }
+    if let Some(answers) = answers {
+        answers.save(&answers_path)?;
+        println!("accepted answers saved to {}", answers_path.display());
+    }
+    return Ok(());
+}
+
+fn verify(day: Option<u8>, inputs_dir: PathBuf, answers_path: PathBuf) -> Result<(), String> {
+    let answers = Answers::load(&answers_path)?;
+    let days: Vec<u8> = match day {
+        Some(day) => vec![day],
+        None => days::registered(),
+    };
+    let checks = verify::verify(&days, &inputs_dir, &answers);
+    print!("{}", verify::format_table(&checks));
+
+    let num_failed = checks.iter().filter(|check| check.failed()).count();
+    if num_failed > 0 {
+        return Err(format!("{} of {} checks failed", num_failed, checks.len()));
+    }
+    return Ok(());
+}
+
+fn bench(
+    day: Option<u8>,
+    runs: usize,
+    inputs_dir: PathBuf,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+) -> Result<(), String> {
+    let baseline: Option<Report> = baseline.map(|path| bench::load_report(&path)).transpose()?;
+    let days: Vec<u8> = match day {
+        Some(day) => vec![day],
+        None => days::registered(),
+    };
+
+    let mut report = Report::new();
+    for day in days {
+        let Some(solution) = days::get(day) else {
+            return Err(format!("no solution registered for day {}", day));
+        };
+        let source = InputSource::for_day(&inputs_dir, day);
+        let input = read_input(&source).map_err(|err| err.to_string())?;
+        let mut timer = RepeatTimer::new(runs);
+        (solution.bench)(&input, &mut timer).map_err(|err| format!("could not parse {}: {}", source, err))?;
+        report.insert(day, timer.stats);
+    }
+    print!("{}", bench::format_report(&report, baseline.as_ref()));
+
+    if let Some(path) = save {
+        bench::save_report(&report, &path)?;
+        println!("timings saved to {}", path.display());
+    }
+    return Ok(());
+}
+
+fn day02(command: Day02Command) -> Result<(), String> {
+    match command {
+        Day02Command::Feasible { bag, input } => {
+            let (source, input) = input.read(2)?;
+            let games = day_2::parse(&input).map_err(|err| format!("could not parse {}: {}", source, err))?;
+            print!("{}", day02::format_checks(&day_2::check_games(&games, &bag)));
+        }
+        Day02Command::Report { format, input } => {
+            let (source, input) = input.read(2)?;
+            let games = day_2::parse(&input).map_err(|err| format!("could not parse {}: {}", source, err))?;
+            let stats: Vec<day_2::GameStats> = games.iter().map(day_2::get_game_stats).collect();
+            let summary = day_2::get_summary(&stats);
+            match format {
+                OutputFormat::Table => print!("{}", day02::format_report_table(&stats, &summary)),
+                OutputFormat::Json => print!("{}", day02::format_report_json(&stats, &summary)),
+            }
+        }
+    }
+    return Ok(());
+}
+
+fn day04(command: Day04Command) -> Result<(), String> {
+    match command {
+        Day04Command::Cards { format, input } => {
+            let (source, input) = input.read(4)?;
+            let scratch_cards = Day04::parse(&input).map_err(|err| format!("could not parse {}: {}", source, err))?;
+            let breakdown = day_04::get_breakdown(&scratch_cards).map_err(|err| format!("no breakdown for {}: {}", source, err))?;
+            match format {
+                OutputFormat::Table => print!("{}", day04::format_breakdown_table(&breakdown)),
+                OutputFormat::Json => print!("{}", day04::format_breakdown_json_lines(&breakdown)),
+            }
+        }
+    }
+    return Ok(());
+}
+
+/// Reads and parses the day 05 almanac, refusing ambiguous ones when `strict`.
+fn read_almanac(input: InputArgs, strict: bool) -> Result<Almanac, String> {
+    let (source, input) = input.read(5)?;
+    let almanac = if strict { day_05::parse_strict(&input) } else { Day05::parse(&input) };
+    return almanac.map_err(|err| format!("could not parse {}: {}", source, err));
+}
+
+fn day05(command: Day05Command) -> Result<(), String> {
+    match command {
+        Day05Command::Map { from, to, values, strict, input } => {
+            let almanac = read_almanac(input, strict)?;
+            let map = almanac.categories().get_mapping(&from, &to).map_err(|err| err.to_string())?;
+            if values.is_empty() {
+                print!("{}", day05::format_segments(&map));
+            }
+            for value in values {
+                println!("{} {} -> {} {}", from, value, to, map.get(value));
+            }
+        }
+        Day05Command::Inverse { from, to, ranges, strict, input } => {
+            let almanac = read_almanac(input, strict)?;
+            let preimage = almanac.categories().get_preimage(&to, &from, &ranges).map_err(|err| err.to_string())?;
+            print!("{}", day05::format_ranges(&preimage));
+        }
+        Day05Command::Check { strict, input } => {
+            let (source, input) = input.read(5)?;
+            let issues = day_05::validate(&input).map_err(|err| format!("could not parse {}: {}", source, err))?;
+            print!("{}", day05::format_issues(&issues));
+            if strict && !issues.is_empty() {
+                return Err(format!("{} has {} ambiguous or invalid entries", source, issues.len()));
+            }
+        }
+    }
+    return Ok(());
+}
+
+fn new(day: u8, root: PathBuf) -> Result<(), String> {
+    for path in scaffold::new_day(&root, day)? {
+        println!("wrote {}", path.display());
+    }
+    return Ok(());
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    diagnostics::set_max_level(cli.get_max_level());
+    let result = match cli.command {
+        Command::Run { day, part, input, accept, answers } => run(day, part, input, accept, answers),
+        Command::Verify { day, inputs_dir, answers } => verify(day, inputs_dir, answers),
+        Command::Bench { day, runs, inputs_dir, save, baseline } => bench(day, runs, inputs_dir, save, baseline),
+        Command::Day02 { command } => day02(command),
+        Command::Day04 { command } => day04(command),
+        Command::Day05 { command } => day05(command),
+        Command::New { day, root } => new(day, root),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        return ExitCode::FAILURE;
+    }
+    return ExitCode::SUCCESS;
+}
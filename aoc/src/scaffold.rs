@@ -0,0 +1,180 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::input::INPUT_FILE_NAME;
+
+const TEMPLATE_DIR: &str = "template";
+const TEMPLATE_CRATE_NAME: &str = "day_template";
+const TEMPLATE_STRUCT_NAME: &str = "DayTemplate";
+const TEMPLATE_FILES: [&str; 2] = ["Cargo.toml", "src/lib.rs"];
+const TEST_INPUT_FILE_NAME: &str = "test_input.txt";
+
+fn day_dir_name(day: u8) -> String {
+    return format!("{:02}", day);
+}
+
+fn crate_name(day: u8) -> String {
+    return format!("day_{:02}", day);
+}
+
+fn struct_name(day: u8) -> String {
+    return format!("Day{:02}", day);
+}
+
+fn instantiate_template(template: &str, day: u8) -> String {
+    return template
+        .replace(TEMPLATE_CRATE_NAME, &crate_name(day))
+        .replace(TEMPLATE_STRUCT_NAME, &struct_name(day));
+}
+
+/// Inserts `entry` into `text` among the lines `line_day` recognises as belonging to a day,
+/// keeping them ordered by day. Fails if `day` already has a line or there are no day lines.
+fn insert_day_line(text: &str, day: u8, entry: &str, line_day: impl Fn(&str) -> Option<u8>) -> Result<String, String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.iter().any(|line| line_day(line) == Some(day)) {
+        return Err(format!("day {} is already registered", day));
+    }
+    let Some(last_day_line) = lines.iter().rposition(|line| line_day(line).is_some()) else {
+        return Err("could not find where the days are registered".to_string());
+    };
+    let index = lines
+        .iter()
+        .position(|line| line_day(line).is_some_and(|line_day| line_day > day))
+        .unwrap_or(last_day_line + 1);
+    lines.insert(index, entry);
+    return Ok(lines.join("\n") + "\n");
+}
+
+/// `"05",` in the workspace members list.
+fn member_day(line: &str) -> Option<u8> {
+    let dir_name = line.trim().strip_prefix('"')?.strip_suffix("\",")?;
+    if dir_name.len() != 2 {
+        return None;
+    }
+    return dir_name.parse::<u8>().ok();
+}
+
+/// `day_05 = { path = "../05" }` in the runner's dependencies.
+fn dependency_day(line: &str) -> Option<u8> {
+    let (name, _) = line.strip_prefix("day_")?.split_once(' ')?;
+    return name.parse::<u8>().ok();
+}
+
+/// `5 => return Some(Day::of::<day_05::Day05>()),` in the runner's registry.
+fn dispatch_day(line: &str) -> Option<u8> {
+    let (day, _) = line.trim_start().split_once(" => ")?;
+    return day.parse::<u8>().ok();
+}
+
+/// Adds the day's directory to the workspace `members` list of the root manifest.
+fn register_member(manifest: &str, day: u8) -> Result<String, String> {
+    let entry = format!("    \"{}\",", day_dir_name(day));
+    return insert_day_line(manifest, day, &entry, member_day);
+}
+
+/// Adds the day crate as a path dependency of the runner.
+fn register_dependency(manifest: &str, day: u8) -> Result<String, String> {
+    let entry = format!("{} = {{ path = \"../{}\" }}", crate_name(day), day_dir_name(day));
+    return insert_day_line(manifest, day, &entry, dependency_day);
+}
+
+/// Adds the day's `Solution` to the runner's registry in `days.rs`.
+fn register_solution(days_source: &str, day: u8) -> Result<String, String> {
+    let entry = format!("        {} => return Some(Day::of::<{}::{}>()),", day, crate_name(day), struct_name(day));
+    return insert_day_line(days_source, day, &entry, dispatch_day);
+}
+
+fn update_file(path: &Path, update: impl Fn(&str) -> Result<String, String>) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+    let updated = update(&contents).map_err(|err| format!("{}: {}", path.display(), err))?;
+    return fs::write(path, updated).map_err(|err| format!("could not write {}: {}", path.display(), err));
+}
+
+/// Creates the crate for `day` from the template under the workspace at `root` and registers
+/// it with the workspace and the runner, returning the files it wrote.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let day_dir = root.join(day_dir_name(day));
+    if day_dir.exists() {
+        return Err(format!("{} already exists", day_dir.display()));
+    }
+
+    let runner_manifest_path = root.join("aoc").join("Cargo.toml");
+    let days_source_path = root.join("aoc").join("src").join("days.rs");
+    let workspace_manifest_path = root.join("Cargo.toml");
+    // Work out every edit before touching the tree so a failure leaves it as it was.
+    let read = |path: &Path| fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path.display(), err));
+    register_member(&read(&workspace_manifest_path)?, day)?;
+    register_dependency(&read(&runner_manifest_path)?, day)?;
+    register_solution(&read(&days_source_path)?, day)?;
+
+    let mut written: Vec<PathBuf> = vec![];
+    for file in TEMPLATE_FILES {
+        let template = read(&root.join(TEMPLATE_DIR).join(file))?;
+        let path = day_dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).map_err(|err| format!("could not create {}: {}", day_dir.display(), err))?;
+        fs::write(&path, instantiate_template(&template, day)).map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+        written.push(path);
+    }
+    for file in [INPUT_FILE_NAME, TEST_INPUT_FILE_NAME] {
+        let path = day_dir.join(file);
+        fs::write(&path, "").map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+        written.push(path);
+    }
+
+    update_file(&workspace_manifest_path, |manifest| register_member(manifest, day))?;
+    update_file(&runner_manifest_path, |manifest| register_dependency(manifest, day))?;
+    update_file(&days_source_path, |days_source| register_solution(days_source, day))?;
+    written.extend([workspace_manifest_path, runner_manifest_path, days_source_path]);
+
+    return Ok(written);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instantiates_template_names() {
+        let template = "use day_template::DayTemplate;\nname = \"day_template\"";
+
+        assert_eq!(instantiate_template(template, 10), "use day_10::Day10;\nname = \"day_10\"");
+    }
+
+    #[test]
+    fn registers_member_in_order() {
+        let manifest = "[workspace]\nmembers = [\n    \"01\",\n    \"09\",\n    \"aoc\",\n]\n";
+
+        assert_eq!(
+            register_member(manifest, 10).unwrap(),
+            "[workspace]\nmembers = [\n    \"01\",\n    \"09\",\n    \"10\",\n    \"aoc\",\n]\n"
+        );
+        assert_eq!(
+            register_member(manifest, 5).unwrap(),
+            "[workspace]\nmembers = [\n    \"01\",\n    \"05\",\n    \"09\",\n    \"aoc\",\n]\n"
+        );
+        assert!(register_member(manifest, 9).is_err());
+    }
+
+    #[test]
+    fn registers_runner_dependency() {
+        let manifest = "[dependencies]\nclap = \"4\"\nday_3 = { path = \"../03\" }\nday_09 = { path = \"../09\" }\n\n[lints]\n";
+
+        assert_eq!(
+            register_dependency(manifest, 10).unwrap(),
+            "[dependencies]\nclap = \"4\"\nday_3 = { path = \"../03\" }\nday_09 = { path = \"../09\" }\nday_10 = { path = \"../10\" }\n\n[lints]\n"
+        );
+    }
+
+    #[test]
+    fn registers_solution_before_fallback_arm() {
+        let days_source = "    match day {\n        9 => return Some(Day::of::<day_09::Day09>()),\n        _ => return None,\n    }\n";
+
+        assert_eq!(
+            register_solution(days_source, 10).unwrap(),
+            "    match day {\n        9 => return Some(Day::of::<day_09::Day09>()),\n        10 => return Some(Day::of::<day_10::Day10>()),\n        _ => return None,\n    }\n"
+        );
+        assert!(register_solution(days_source, 9).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+/// Lays out `rows` under `header` in left-aligned columns separated by two spaces, with trailing
+/// whitespace trimmed from every line.
+pub fn format_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let mut widths = header.map(|title| title.chars().count());
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = std::cmp::max(*width, cell.chars().count());
+        }
+    }
+    let header = header.map(|title| title.to_string());
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let cells: Vec<String> = row.iter().zip(widths).map(|(cell, width)| format!("{:<width$}", cell)).collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    return table;
+}
@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use aoc_common::{
+    input::{read_input, InputError, InputSource},
+    SolveError,
+};
+
+use crate::{answers::Answers, days, table};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    /// No accepted answer is recorded for this input.
+    Missing,
+    /// The day's input could not be read or parsed.
+    Error(String),
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn failed(&self) -> bool {
+        return matches!(self.status, Status::Fail { .. } | Status::Error(_));
+    }
+}
+
+fn check_part(day: u8, part: u8, input: &str, answer: Result<String, SolveError>, answers: &Answers) -> Check {
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(err) => return Check { day, part, answer: None, status: Status::Error(err.to_string()) },
+    };
+    let status = match answers.get(day, part, input) {
+        None => Status::Missing,
+        Some(expected) if expected == answer => Status::Pass,
+        Some(expected) => Status::Fail { expected: expected.to_string() },
+    };
+    return Check { day, part, answer: Some(answer), status };
+}
+
+/// Runs both parts of `day` against `input`, parsing it once, and compares the answers with the
+/// accepted ones.
+fn check_day(day: u8, input: &str, answers: &Answers) -> Vec<Check> {
+    let error = |message: String| [1, 2].map(|part| Check { day, part, answer: None, status: Status::Error(message.clone()) });
+    let answers_by_part = match days::run(day, &[1, 2], input) {
+        None => return error("no solution registered".to_string()).into(),
+        Some(Err(err)) => return error(err.to_string()).into(),
+        Some(Ok(answers_by_part)) => answers_by_part,
+    };
+    return [1, 2]
+        .into_iter()
+        .zip(answers_by_part)
+        .map(|(part, answer)| check_part(day, part, input, answer, answers))
+        .collect();
+}
+
+/// Runs both parts of each of `days` against its input under `inputs_dir` and compares the
+/// answers with the accepted ones.
+pub fn verify(days: &[u8], inputs_dir: &Path, answers: &Answers) -> Vec<Check> {
+    let mut checks: Vec<Check> = vec![];
+    for day in days {
+        let input = match read_input(&InputSource::for_day(inputs_dir, *day)) {
+            Ok(input) => input,
+            Err(err) => {
+                let message = match err {
+                    InputError::Missing(_) => "no input".to_string(),
+                    err => err.to_string(),
+                };
+                checks.extend([1, 2].map(|part| Check { day: *day, part, answer: None, status: Status::Error(message.clone()) }));
+                continue;
+            }
+        };
+        checks.extend(check_day(*day, &input, answers));
+    }
+    return checks;
+}
+
+/// Formats `checks` as a table, one row per day and part.
+pub fn format_table(checks: &[Check]) -> String {
+    let rows: Vec<[String; 5]> = checks
+        .iter()
+        .map(|check| {
+            let (status, detail) = match &check.status {
+                Status::Pass => ("pass", "".to_string()),
+                Status::Fail { expected } => ("FAIL", format!("expected {}", expected)),
+                Status::Missing => ("missing", "".to_string()),
+                Status::Error(message) => ("ERROR", message.clone()),
+            };
+            return [
+                format!("{:02}", check.day),
+                check.part.to_string(),
+                status.to_string(),
+                check.answer.clone().unwrap_or_default(),
+                detail,
+            ];
+        })
+        .collect();
+
+    return table::format_table(["Day", "Part", "Status", "Answer", ""], &rows);
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::diagnostics::Level;
+
+    use super::*;
+
+    #[test]
+    fn compares_against_accepted_answers() {
+        let input = include_str!("../../09/test_input.txt");
+        let mut answers = Answers::default();
+        answers.insert(9, 1, input, "114".to_string());
+        answers.insert(9, 2, input, "3".to_string());
+
+        let statuses: Vec<Status> = check_day(9, input, &answers).into_iter().map(|check| check.status).collect();
+        assert_eq!(statuses, vec![Status::Pass, Status::Fail { expected: "3".to_string() }]);
+        assert_eq!(check_day(9, input, &Answers::default())[1].status, Status::Missing);
+    }
+
+    #[test]
+    fn parts_without_an_answer_fail() {
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+
+        let checks = check_day(8, input, &Answers::default());
+        assert!(checks.iter().all(|check| check.failed()));
+        assert_eq!(checks[0].status, Status::Error("the walk from AAA never reaches an end node".to_string()));
+    }
+
+    #[test]
+    fn parses_each_day_once() {
+        let input = "seeds: 79 14\n\nseed-to-location map:\n# comment\n50 98 2\n";
+
+        let (checks, diagnostics) = aoc_common::diagnostics::capture(|| check_day(5, input, &Answers::default()));
+        assert_eq!(checks.len(), 2);
+        assert_eq!(diagnostics.iter().filter(|diagnostic| diagnostic.level == Level::Warn).count(), 1);
+    }
+
+    #[test]
+    fn committed_answers_pass() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let answers = Answers::load(&root.join(crate::answers::ANSWERS_FILE_NAME)).unwrap();
+
+        let checks = verify(&days::registered(), &root, &answers);
+        assert!(checks.iter().all(|check| check.status == Status::Pass), "{}", format_table(&checks));
+    }
+
+    #[test]
+    fn formats_aligned_table() {
+        let checks = vec![
+            Check { day: 9, part: 1, answer: Some("114".to_string()), status: Status::Pass },
+            Check { day: 9, part: 2, answer: Some("2".to_string()), status: Status::Fail { expected: "3".to_string() } },
+        ];
+
+        assert_eq!(
+            format_table(&checks),
+            "Day  Part  Status  Answer\n09   1     pass    114\n09   2     FAIL    2       expected 3\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// How much a diagnostic matters, from most to least important.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "error",
+            Level::Warn => "warning",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A message from a solution about its input or its progress, kept off stdout so it never mixes
+/// with answers or machine-readable output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.level, self.message)
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<Diagnostic>>> = const { RefCell::new(None) };
+}
+
+/// Sets the least important level printed to stderr; `Warn` unless changed.
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether a diagnostic at `level` would go anywhere, to skip building expensive messages.
+pub fn is_enabled(level: Level) -> bool {
+    return level as u8 <= MAX_LEVEL.load(Ordering::Relaxed) || CAPTURED.with(|captured| captured.borrow().is_some());
+}
+
+/// Reports `message` at `level`: collected if inside [`capture`] on this thread, otherwise
+/// printed to stderr when `level` is at or above the maximum level.
+pub fn emit(level: Level, message: impl fmt::Display) {
+    if !is_enabled(level) {
+        return;
+    }
+    let diagnostic = Diagnostic { level, message: message.to_string() };
+    let unclaimed = CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(diagnostics) => {
+            diagnostics.push(diagnostic);
+            return None;
+        }
+        None => return Some(diagnostic),
+    });
+    if let Some(diagnostic) = unclaimed {
+        eprintln!("{}", diagnostic);
+    }
+}
+
+pub fn warn(message: impl fmt::Display) {
+    emit(Level::Warn, message);
+}
+
+pub fn info(message: impl fmt::Display) {
+    emit(Level::Info, message);
+}
+
+pub fn debug(message: impl fmt::Display) {
+    emit(Level::Debug, message);
+}
+
+/// Runs `f`, returning its result together with every diagnostic it reported, whatever the
+/// maximum level. Nothing reported inside is printed.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<Diagnostic>) {
+    let outer = CAPTURED.with(|captured| captured.replace(Some(vec![])));
+    let result = f();
+    let diagnostics = CAPTURED.with(|captured| captured.replace(outer)).unwrap_or_default();
+    return (result, diagnostics);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_every_level_in_order() {
+        let (answer, diagnostics) = capture(|| {
+            warn("odd input");
+            debug(format_args!("step {}", 3));
+            return 42;
+        });
+
+        assert_eq!(answer, 42);
+        assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<String>>(), vec!["warning: odd input", "debug: step 3"]);
+    }
+
+    #[test]
+    fn nested_capture_keeps_its_own_diagnostics() {
+        let ((_, inner), outer) = capture(|| {
+            info("outer");
+            return capture(|| info("inner"));
+        });
+
+        assert_eq!((inner.len(), outer.len()), (1, 1));
+        assert_eq!(inner[0].message, "inner");
+    }
+}
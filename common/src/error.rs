@@ -0,0 +1,117 @@
+use std::fmt;
+
+/// A malformed puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column (in characters) where `text` starts.
+    pub column: usize,
+    /// The text that could not be parsed.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> ParseError {
+        return ParseError { line, column, text: text.to_string(), message: message.into() };
+    }
+
+    /// Error for `text`, a slice of `line` (the `line_index`th line of the input, counting from
+    /// 0), with the column worked out from where `text` sits in `line`.
+    pub fn in_line(line_index: usize, line: &str, text: &str, message: impl Into<String>) -> ParseError {
+        return ParseError::new(line_index + 1, column_of(line, text), text, message);
+    }
+}
+
+/// 1-based character column at which the subslice `text` starts in `line`, or 1 when `text`
+/// does not point into `line`.
+pub fn column_of(line: &str, text: &str) -> usize {
+    let line_start = line.as_ptr() as usize;
+    let text_start = text.as_ptr() as usize;
+    if text_start < line_start || text_start + text.len() > line_start + line.len() {
+        return 1;
+    }
+    let byte_offset = text_start - line_start;
+    return line[..byte_offset].chars().count() + 1;
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {} (at {:?})", self.line, self.column, self.message, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a part has no answer for an input that parsed fine, e.g. because the answer does not fit
+/// in the part's answer type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> SolveError {
+        return SolveError { message: message.into() };
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Why a part could not be answered from the raw puzzle input: either step can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        return Error::Parse(err);
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(err: SolveError) -> Error {
+        return Error::Solve(err);
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "{}", err),
+            Error::Solve(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_is_found_from_subslice() {
+        let line = "Card 1: 41 48 | x3";
+        let err = ParseError::in_line(2, line, &line[16..], "expected a number");
+
+        assert_eq!(err.line, 3);
+        assert_eq!(err.column, 17);
+        assert_eq!(err.text, "x3");
+        assert_eq!(err.to_string(), "line 3, column 17: expected a number (at \"x3\")");
+    }
+
+    #[test]
+    fn column_of_unrelated_text_is_first_column() {
+        assert_eq!(column_of("abc", "zzz"), 1);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut, RangeInclusive},
+};
+
+use crate::ParseError;
+
+/// A `(row, col)` position in a [`Grid`], counting from the top left.
+pub type Position = (usize, usize);
+
+/// Offsets to the four orthogonal neighbours: up, left, right, down.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+/// Offsets to all eight neighbours, row by row.
+const SURROUNDING: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// A rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row order, or `None` if they do not fill `width` columns
+    /// evenly.
+    pub fn new(width: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+        return Some(Grid { width, height: cells.len() / width, cells });
+    }
+
+    /// Parses one cell per character, one row per line. Every line must be as long as the first.
+    pub fn parse_with(input: &str, cell: impl Fn(char) -> Result<T, String>) -> Result<Grid<T>, ParseError> {
+        let mut width: Option<usize> = None;
+        let mut cells: Vec<T> = vec![];
+        for (row, line) in input.lines().enumerate() {
+            let row_width = line.chars().count();
+            let expected_width = *width.get_or_insert(row_width);
+            if row_width != expected_width {
+                return Err(ParseError::new(
+                    row + 1,
+                    1,
+                    line,
+                    format!("expected a row of {} cells, found {}", expected_width, row_width),
+                ));
+            }
+            for (col, char) in line.chars().enumerate() {
+                cells.push(cell(char).map_err(|err| ParseError::new(row + 1, col + 1, &char.to_string(), err))?);
+            }
+        }
+        return match width {
+            Some(width) if width > 0 => Ok(Grid::new(width, cells).unwrap()),
+            _ => Err(ParseError::new(1, 1, "", "grid is empty")),
+        };
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        return row < self.height && col < self.width;
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if !self.contains(position) {
+            return None;
+        }
+        return Some(&self.cells[position.0 * self.width + position.1]);
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if !self.contains(position) {
+            return None;
+        }
+        return Some(&mut self.cells[position.0 * self.width + position.1]);
+    }
+
+    /// `position` moved by `(row, col)` offsets, if that stays inside the grid.
+    pub fn offset(&self, (row, col): Position, (row_offset, col_offset): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(row_offset)?, col.checked_add_signed(col_offset)?);
+        return self.contains(position).then_some(position);
+    }
+
+    /// The up to four cells sharing an edge with `position`.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        return ORTHOGONAL.into_iter().filter_map(move |offset| self.offset(position, offset));
+    }
+
+    /// The up to eight cells sharing an edge or a corner with `position`.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        return SURROUNDING.into_iter().filter_map(move |offset| self.offset(position, offset));
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return self.cells.chunks_exact(self.width);
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        return self.rows().nth(row);
+    }
+
+    /// The cells of column `col` from top to bottom, empty if `col` is outside the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let cells: &[T] = if col < self.width { &self.cells[col..] } else { &[] };
+        return cells.iter().step_by(self.width);
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (Position, &T)> {
+        return self.cells.iter().enumerate().map(|(index, cell)| ((index / self.width, index % self.width), cell));
+    }
+
+    /// The cells in the given rows and columns, clipped to the grid, row by row.
+    pub fn region(&self, rows: RangeInclusive<usize>, cols: RangeInclusive<usize>) -> impl Iterator<Item = (Position, &T)> {
+        let rows = *rows.start()..=(*rows.end()).min(self.height.saturating_sub(1));
+        let cols = *cols.start()..=(*cols.end()).min(self.width - 1);
+        return rows.flat_map(move |row| cols.clone().map(move |col| ((row, col), &self[(row, col)])));
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        return Grid::parse_with(input, Ok);
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        return self.get(position).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", position, self.width, self.height));
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        return self.get_mut(position).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", position, width, height));
+    }
+}
+
+/// Prints one line per row with each cell's `Display` output, e.g. the puzzle input back again.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "ab.\n.c.\n..d\n";
+
+    #[test]
+    fn parses_and_prints() {
+        let grid = Grid::parse(INPUT).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get((1, 1)), Some(&'c'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), INPUT);
+    }
+
+    #[test]
+    fn rejects_ragged_and_unknown_cells() {
+        let err = Grid::parse("ab.\n.c\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = Grid::parse_with("01\n2x", |c| c.to_digit(10).ok_or(format!("expected a digit, found {:?}", c))).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid::parse(INPUT).unwrap();
+
+        assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<Position>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors8((0, 0)).collect::<Vec<Position>>(), vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn iterates_rows_columns_and_regions() {
+        let grid = Grid::parse(INPUT).unwrap();
+
+        assert_eq!(grid.row(2), Some(&['.', '.', 'd'][..]));
+        assert_eq!(grid.column(1).collect::<String>(), "bc.");
+        assert_eq!(grid.column(3).count(), 0);
+        let region: String = grid.region(1..=5, 1..=5).map(|(_, cell)| cell).collect();
+        assert_eq!(region, "c..d");
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// File name of a day's puzzle input inside its directory, e.g. `05/input.txt`.
+pub const INPUT_FILE_NAME: &str = "input.txt";
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// The conventional input for `day` under `inputs_dir`: `<inputs_dir>/<day:02>/input.txt`.
+    pub fn for_day(inputs_dir: &Path, day: u8) -> InputSource {
+        return InputSource::File(inputs_dir.join(format!("{:02}", day)).join(INPUT_FILE_NAME));
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = std::convert::Infallible;
+
+    /// `-` means stdin, anything else is a file path.
+    fn from_str(arg: &str) -> Result<InputSource, Self::Err> {
+        if arg == "-" {
+            return Ok(InputSource::Stdin);
+        }
+        return Ok(InputSource::File(PathBuf::from(arg)));
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The input file does not exist.
+    Missing(PathBuf),
+    /// The input exists but could not be read.
+    Unreadable { source: InputSource, error: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(
+                f,
+                "input file {} does not exist (pass --input <path>, or - to read stdin)",
+                path.display()
+            ),
+            InputError::Unreadable { source, error } => {
+                write!(f, "could not read input from {}: {}", source, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing(_) => return None,
+            InputError::Unreadable { error, .. } => return Some(error),
+        }
+    }
+}
+
+/// Reads the whole puzzle input from `source`.
+pub fn read_input(source: &InputSource) -> Result<String, InputError> {
+    let unreadable = |error: io::Error| InputError::Unreadable { source: source.clone(), error };
+    match source {
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map_err(unreadable)?;
+            return Ok(input);
+        }
+        InputSource::File(path) => match fs::read_to_string(path) {
+            Ok(input) => return Ok(input),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Err(InputError::Missing(path.clone()));
+            }
+            Err(error) => return Err(unreadable(error)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dash_as_stdin() {
+        assert_eq!("-".parse::<InputSource>(), Ok(InputSource::Stdin));
+        assert_eq!(
+            "05/test_input.txt".parse::<InputSource>(),
+            Ok(InputSource::File(PathBuf::from("05/test_input.txt")))
+        );
+    }
+
+    #[test]
+    fn day_input_lives_in_zero_padded_directory() {
+        assert_eq!(
+            InputSource::for_day(Path::new("inputs"), 5),
+            InputSource::File(PathBuf::from("inputs/05/input.txt"))
+        );
+    }
+
+    #[test]
+    fn reads_file_input() {
+        let path = std::env::temp_dir().join(format!("aoc-common-input-{}.txt", std::process::id()));
+        fs::write(&path, "1 2 3\n").unwrap();
+
+        let input = read_input(&InputSource::File(path.clone()));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(input.unwrap(), "1 2 3\n");
+    }
+
+    #[test]
+    fn missing_file_names_the_path() {
+        let path = PathBuf::from("does/not/exist/input.txt");
+        let err = read_input(&InputSource::File(path.clone())).unwrap_err();
+
+        assert!(matches!(err, InputError::Missing(ref missing) if *missing == path));
+        assert!(err.to_string().contains("does/not/exist/input.txt"));
+    }
+}
@@ -0,0 +1,96 @@
+pub mod diagnostics;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod parse;
+
+use std::fmt::Display;
+
+pub use error::{Error, ParseError, SolveError};
+
+/// A day's puzzle solution, split into a parse step shared by both parts and the two parts
+/// themselves, so tooling can drive (and time) every day the same way.
+pub trait Solution {
+    /// Parsed form of the puzzle input handed to both parts.
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, SolveError>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, SolveError>;
+}
+
+/// The formatted answer to each requested part, or why it has none, or why the input could not
+/// be parsed at all.
+pub type Solved = Result<Vec<Result<String, SolveError>>, ParseError>;
+
+/// Parses `input` once and runs each of the requested `parts` (1 or 2) of `S` on it, returning
+/// the formatted answers in the same order.
+pub fn solve<S: Solution>(parts: &[u8], input: &str) -> Solved {
+    let parsed = S::parse(input)?;
+    return Ok(parts
+        .iter()
+        .map(|part| match part {
+            1 => S::part1(&parsed).map(|answer| answer.to_string()),
+            2 => S::part2(&parsed).map(|answer| answer.to_string()),
+            _ => Err(SolveError::new(format!("there is no part {}", part))),
+        })
+        .collect());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        type Input = Vec<String>;
+        type Answer1 = usize;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+            return input
+                .lines()
+                .enumerate()
+                .map(|(line_index, line)| match line.is_empty() {
+                    true => Err(ParseError::in_line(line_index, line, line, "empty line")),
+                    false => Ok(line.to_string()),
+                })
+                .collect();
+        }
+
+        fn part1(lines: &Vec<String>) -> Result<usize, SolveError> {
+            return Ok(lines.len());
+        }
+
+        fn part2(lines: &Vec<String>) -> Result<String, SolveError> {
+            if lines.len() < 2 {
+                return Err(SolveError::new("nothing to join"));
+            }
+            return Ok(lines.join(","));
+        }
+    }
+
+    #[test]
+    fn solve_dispatches_parts() {
+        assert_eq!(
+            solve::<LineCount>(&[1, 2, 3], "a\nb"),
+            Ok(vec![Ok("2".to_string()), Ok("a,b".to_string()), Err(SolveError::new("there is no part 3"))])
+        );
+        assert_eq!(solve::<LineCount>(&[2], "a\nb"), Ok(vec![Ok("a,b".to_string())]));
+    }
+
+    #[test]
+    fn solve_reports_parts_without_an_answer() {
+        assert_eq!(solve::<LineCount>(&[1, 2], "a"), Ok(vec![Ok("1".to_string()), Err(SolveError::new("nothing to join"))]));
+    }
+
+    #[test]
+    fn solve_reports_parse_errors() {
+        let err = solve::<LineCount>(&[1, 2], "a\n\nb").unwrap_err();
+
+        assert_eq!(err.line, 2);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{collections::HashSet, fmt, hash::Hash, num::ParseIntError, str::FromStr};
+
+use crate::ParseError;
+
+/// Any of the primitive integer types, signed or unsigned.
+pub trait Integer: FromStr<Err = ParseIntError> + Copy {}
+
+impl<T: FromStr<Err = ParseIntError> + Copy> Integer for T {}
+
+/// `text` (a slice of the `line_index`th line `line`) as a single integer.
+pub fn integer<T: Integer>(line_index: usize, line: &str, text: &str) -> Result<T, ParseError> {
+    return text
+        .parse::<T>()
+        .map_err(|err| ParseError::in_line(line_index, line, text, err.to_string()));
+}
+
+/// The whitespace-separated integers in `text`, a slice of the `line_index`th line `line`.
+/// Unsigned types reject a leading `-`, so errors point at the offending word.
+pub fn integers<T: Integer>(line_index: usize, line: &str, text: &str) -> Result<Vec<T>, ParseError> {
+    return text
+        .split_whitespace()
+        .map(|number_str| integer(line_index, line, number_str))
+        .collect();
+}
+
+/// Like [`integers`], but rejects a number that appears more than once, pointing at the repeat.
+pub fn unique_integers<T: Integer + Eq + Hash + fmt::Display>(line_index: usize, line: &str, text: &str) -> Result<Vec<T>, ParseError> {
+    let mut seen: HashSet<T> = HashSet::new();
+    let mut numbers: Vec<T> = vec![];
+    for number_str in text.split_whitespace() {
+        let number: T = integer(line_index, line, number_str)?;
+        if !seen.insert(number) {
+            return Err(ParseError::in_line(line_index, line, number_str, format!("{} appears more than once", number)));
+        }
+        numbers.push(number);
+    }
+    return Ok(numbers);
+}
+
+/// Exactly `N` whitespace-separated integers in `text`, e.g. a `destination source length` row.
+pub fn tuple<T: Integer, const N: usize>(line_index: usize, line: &str, text: &str) -> Result<[T; N], ParseError> {
+    let numbers: Vec<T> = integers(line_index, line, text)?;
+    let found = numbers.len();
+    return numbers
+        .try_into()
+        .map_err(|_| ParseError::in_line(line_index, line, text, format!("expected {} numbers, found {}", N, found)));
+}
+
+/// The rest of `line` after `label:`, e.g. the numbers of `seeds: 79 14 55 13`.
+pub fn labelled<'a>(line_index: usize, line: &'a str, label: &str) -> Result<&'a str, ParseError> {
+    let Some(rest) = line.strip_prefix(label).and_then(|rest| rest.strip_prefix(':')) else {
+        return Err(ParseError::in_line(line_index, line, line, format!("expected a line starting with `{}:`", label)));
+    };
+    return Ok(rest);
+}
+
+/// The integers following `label:` on `line`.
+pub fn labelled_integers<T: Integer>(line_index: usize, line: &str, label: &str) -> Result<Vec<T>, ParseError> {
+    return integers(line_index, line, labelled(line_index, line, label)?);
+}
+
+/// A `key: numbers | numbers` line, such as `Card 1: 41 48 83 | 83 86 6`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct KeyedLists<'a, T> {
+    pub key: &'a str,
+    pub left: Vec<T>,
+    pub right: Vec<T>,
+}
+
+/// The key and the unparsed left and right lists of a `key: numbers | numbers` line.
+pub fn split_keyed_lists(line_index: usize, line: &str) -> Result<(&str, &str, &str), ParseError> {
+    let Some((key, lists)) = line.split_once(':') else {
+        return Err(ParseError::in_line(line_index, line, line, "expected `<key>: <numbers> | <numbers>`"));
+    };
+    let Some((left, right)) = lists.split_once('|') else {
+        return Err(ParseError::in_line(line_index, line, lists, "expected two lists of numbers separated by `|`"));
+    };
+    return Ok((key, left, right));
+}
+
+pub fn keyed_lists<T: Integer>(line_index: usize, line: &str) -> Result<KeyedLists<'_, T>, ParseError> {
+    let (key, left, right) = split_keyed_lists(line_index, line)?;
+    return Ok(KeyedLists {
+        key,
+        left: integers(line_index, line, left)?,
+        right: integers(line_index, line, right)?,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_signed_and_unsigned() {
+        let line = "0 -3 6  9";
+
+        assert_eq!(integers::<i32>(0, line, line), Ok(vec![0, -3, 6, 9]));
+        let err = integers::<u32>(4, line, line).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 3, "-3"));
+    }
+
+    #[test]
+    fn unique_rejects_repeats() {
+        let line = "41 48 83 48";
+
+        assert_eq!(unique_integers::<u32>(0, line, &line[..8]), Ok(vec![41, 48, 83]));
+        let err = unique_integers::<u32>(0, line, line).unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (10, "48 appears more than once"));
+    }
+
+    #[test]
+    fn tuple_checks_arity() {
+        assert_eq!(tuple::<u64, 3>(0, "50 98 2", "50 98 2"), Ok([50, 98, 2]));
+
+        let err = tuple::<u64, 3>(0, "50 98", "50 98").unwrap_err();
+        assert_eq!(err.message, "expected 3 numbers, found 2");
+    }
+
+    #[test]
+    fn labelled_requires_label() {
+        assert_eq!(labelled_integers::<u64>(0, "seeds: 79 14", "seeds"), Ok(vec![79, 14]));
+        assert!(labelled_integers::<u64>(0, "seed: 79 14", "seeds").is_err());
+    }
+
+    #[test]
+    fn splits_keyed_lists() {
+        let line = "Card 1: 41 48 | 83 86 6";
+
+        assert_eq!(keyed_lists::<u32>(0, line), Ok(KeyedLists { key: "Card 1", left: vec![41, 48], right: vec![83, 86, 6] }));
+        let err = keyed_lists::<u32>(0, "Card 1: 41 48 83").unwrap_err();
+        assert_eq!(err.text, " 41 48 83");
+    }
+}
@@ -1,9 +1,31 @@
-pub fn part1(input: &str) -> u32 {
-    return 0;
+use aoc_common::{Error, ParseError, SolveError, Solution};
+
+pub fn part1(input: &str) -> Result<u32, Error> {
+    return Ok(DayTemplate::part1(&DayTemplate::parse(input)?)?);
+}
+
+pub fn part2(input: &str) -> Result<u32, Error> {
+    return Ok(DayTemplate::part2(&DayTemplate::parse(input)?)?);
 }
 
-pub fn part2(input: &str) -> u32 {
-    return 0;
+pub struct DayTemplate;
+
+impl Solution for DayTemplate {
+    type Input = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        return Ok(input.lines().map(|line| line.to_string()).collect());
+    }
+
+    fn part1(_lines: &Vec<String>) -> Result<u32, SolveError> {
+        return Ok(0);
+    }
+
+    fn part2(_lines: &Vec<String>) -> Result<u32, SolveError> {
+        return Ok(0);
+    }
 }
 
 #[cfg(test)]
@@ -14,13 +36,13 @@ mod tests {
     fn part1_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part1(input), 0)
+        assert_eq!(part1(input), Ok(0))
     }
 
     #[test]
     fn part2_example() {
         let input = include_str!("../test_input.txt");
 
-        assert_eq!(part2(input), 0);
+        assert_eq!(part2(input), Ok(0));
     }
 }